@@ -78,6 +78,16 @@ macro_rules! new_full_start {
 				import_setup = Some((grandpa_block_import, grandpa_link));
 
 				Ok(import_queue)
+			})?
+			.with_rpc_extensions(|builder| -> Result<jsonrpc_core::IoHandler<sc_rpc::Metadata>, sc_service::Error> {
+				use pallet_poe_rpc::{Poe, PoeApi};
+
+				let mut io = jsonrpc_core::IoHandler::default();
+				io.extend_with(PoeApi::<_, node_template_runtime::AccountId, node_template_runtime::BlockNumber, node_template_runtime::Moment>::to_delegate(
+					Poe::new(builder.client().clone()),
+				));
+
+				Ok(io)
 			})?;
 
 		(builder, import_setup, inherent_data_providers)
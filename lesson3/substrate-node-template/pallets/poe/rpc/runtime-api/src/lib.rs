@@ -0,0 +1,58 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_poe::{ClaimRecord, ClaimStatus};
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber, Moment> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Number of stored claims per length bucket, as `(bucket, count)` pairs.
+		fn claim_length_histogram() -> Vec<(u32, u32)>;
+
+		/// Up to `limit` claims starting with `prefix`.
+		fn claims_with_prefix(prefix: Vec<u8>, limit: u32) -> Vec<Vec<u8>>;
+
+		/// Claims with the smallest and largest stored block number.
+		fn claim_bounds() -> (Option<(Vec<u8>, BlockNumber)>, Option<(Vec<u8>, BlockNumber)>);
+
+		/// Full record of a claim, `None` if it does not exist.
+		fn claim_record(claim: Vec<u8>) -> Option<ClaimRecord<AccountId, BlockNumber>>;
+
+		/// Owner of a claim and the recipient of its pending transfer request.
+		fn effective_ownership(claim: Vec<u8>) -> Option<(AccountId, Option<AccountId>)>;
+
+		/// Records of up to `MAX_QUERY_LIMIT` claims, in the order given.
+		fn claim_records(claims: Vec<Vec<u8>>) -> Vec<Option<ClaimRecord<AccountId, BlockNumber>>>;
+
+		/// Owners of up to `MAX_QUERY_LIMIT` claims, in the order given, `None` for absent ones.
+		fn ownership_of(claims: Vec<Vec<u8>>) -> Vec<Option<AccountId>>;
+
+		/// SCALE encoded `pallet_poe::ClaimDebug` of a claim, `None` if it does not exist.
+		fn claim_debug(claim: Vec<u8>) -> Option<Vec<u8>>;
+
+		/// Index and name of every pallet error.
+		fn errors() -> Vec<(u8, Vec<u8>)>;
+
+		/// Timestamp of the block a claim was created in.
+		fn claim_timestamp(claim: Vec<u8>) -> Option<Moment>;
+
+		/// Up to `limit` claims after the storage key `start_key`, with the cursor of the next page.
+		fn all_claims(
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<(Vec<u8>, AccountId, BlockNumber)>, Option<Vec<u8>>);
+
+		/// Number of claims held by `owner` with the oldest and newest of their block numbers.
+		fn owner_stats(owner: AccountId) -> (u32, Option<BlockNumber>, Option<BlockNumber>);
+
+		/// Whether a claim is pending, active or dormant, `None` if it does not exist.
+		fn claim_status(claim: Vec<u8>) -> Option<ClaimStatus>;
+	}
+}
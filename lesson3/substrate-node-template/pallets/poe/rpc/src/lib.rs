@@ -0,0 +1,223 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::sync::Arc;
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_poe_rpc_runtime_api::{ClaimRecord, ClaimStatus, PoeApi as PoeRuntimeApi};
+
+/// Oldest and newest claims with their block numbers.
+pub type ClaimBounds<BlockNumber> = (Option<(Vec<u8>, BlockNumber)>, Option<(Vec<u8>, BlockNumber)>);
+
+/// A page of `(claim, owner, block_number)` entries and the cursor of the next page.
+pub type ClaimPage<AccountId, BlockNumber> = (Vec<(Vec<u8>, AccountId, BlockNumber)>, Option<Vec<u8>>);
+
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber, Moment> {
+	#[rpc(name = "poe_claimLengthHistogram")]
+	fn claim_length_histogram(&self, at: Option<BlockHash>) -> Result<Vec<(u32, u32)>>;
+
+	#[rpc(name = "poe_claimsWithPrefix")]
+	fn claims_with_prefix(&self, prefix: Vec<u8>, limit: u32, at: Option<BlockHash>) -> Result<Vec<Vec<u8>>>;
+
+	#[rpc(name = "poe_claimBounds")]
+	fn claim_bounds(&self, at: Option<BlockHash>) -> Result<ClaimBounds<BlockNumber>>;
+
+	#[rpc(name = "poe_claimRecord")]
+	fn claim_record(&self, claim: Vec<u8>, at: Option<BlockHash>) -> Result<Option<ClaimRecord<AccountId, BlockNumber>>>;
+
+	#[rpc(name = "poe_effectiveOwnership")]
+	fn effective_ownership(
+		&self,
+		claim: Vec<u8>,
+		at: Option<BlockHash>,
+	) -> Result<Option<(AccountId, Option<AccountId>)>>;
+
+	#[rpc(name = "poe_claimRecords")]
+	fn claim_records(
+		&self,
+		claims: Vec<Vec<u8>>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Option<ClaimRecord<AccountId, BlockNumber>>>>;
+
+	#[rpc(name = "poe_ownershipOf")]
+	fn ownership_of(&self, claims: Vec<Vec<u8>>, at: Option<BlockHash>) -> Result<Vec<Option<AccountId>>>;
+
+	#[rpc(name = "poe_claimDebug")]
+	fn claim_debug(&self, claim: Vec<u8>, at: Option<BlockHash>) -> Result<Option<Vec<u8>>>;
+
+	#[rpc(name = "poe_errors")]
+	fn errors(&self, at: Option<BlockHash>) -> Result<Vec<(u8, Vec<u8>)>>;
+
+	#[rpc(name = "poe_claimTimestamp")]
+	fn claim_timestamp(&self, claim: Vec<u8>, at: Option<BlockHash>) -> Result<Option<Moment>>;
+
+	#[rpc(name = "poe_allClaims")]
+	fn all_claims(
+		&self,
+		start_key: Option<Vec<u8>>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<ClaimPage<AccountId, BlockNumber>>;
+
+	#[rpc(name = "poe_ownerStats")]
+	fn owner_stats(&self, owner: AccountId, at: Option<BlockHash>) -> Result<(u32, Option<BlockNumber>, Option<BlockNumber>)>;
+
+	#[rpc(name = "poe_claimStatus")]
+	fn claim_status(&self, claim: Vec<u8>, at: Option<BlockHash>) -> Result<Option<ClaimStatus>>;
+}
+
+/// A struct that implements the `PoeApi`.
+pub struct Poe<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Poe<C, B> {
+	/// Create new `Poe` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Poe { client, _marker: Default::default() }
+	}
+}
+
+/// Error code used when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query proof of existence state.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Moment> PoeApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Moment> for Poe<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+	Moment: Codec,
+{
+	fn claim_length_histogram(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(u32, u32)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_length_histogram(&at).map_err(runtime_error)
+	}
+
+	fn claims_with_prefix(
+		&self,
+		prefix: Vec<u8>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Vec<u8>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claims_with_prefix(&at, prefix, limit).map_err(runtime_error)
+	}
+
+	fn claim_bounds(&self, at: Option<<Block as BlockT>::Hash>) -> Result<ClaimBounds<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_bounds(&at).map_err(runtime_error)
+	}
+
+	fn claim_record(
+		&self,
+		claim: Vec<u8>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimRecord<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_record(&at, claim).map_err(runtime_error)
+	}
+
+	fn effective_ownership(
+		&self,
+		claim: Vec<u8>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(AccountId, Option<AccountId>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.effective_ownership(&at, claim).map_err(runtime_error)
+	}
+
+	fn errors(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(u8, Vec<u8>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.errors(&at).map_err(runtime_error)
+	}
+
+	fn claim_timestamp(&self, claim: Vec<u8>, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Moment>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_timestamp(&at, claim).map_err(runtime_error)
+	}
+
+	fn all_claims(
+		&self,
+		start_key: Option<Vec<u8>>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ClaimPage<AccountId, BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.all_claims(&at, start_key, limit).map_err(runtime_error)
+	}
+
+	fn owner_stats(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<(u32, Option<BlockNumber>, Option<BlockNumber>)> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.owner_stats(&at, owner).map_err(runtime_error)
+	}
+
+	fn claim_records(
+		&self,
+		claims: Vec<Vec<u8>>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Option<ClaimRecord<AccountId, BlockNumber>>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_records(&at, claims).map_err(runtime_error)
+	}
+
+	fn ownership_of(&self, claims: Vec<Vec<u8>>, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<Option<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.ownership_of(&at, claims).map_err(runtime_error)
+	}
+
+	fn claim_debug(&self, claim: Vec<u8>, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Vec<u8>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_debug(&at, claim).map_err(runtime_error)
+	}
+
+	fn claim_status(&self, claim: Vec<u8>, at: Option<<Block as BlockT>::Hash>) -> Result<Option<ClaimStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_status(&at, claim).map_err(runtime_error)
+	}
+}
@@ -2,13 +2,23 @@
 
 /// A FRAME pallet proof of existence with necessary imports
 
+use codec::{Encode, Decode};
 use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure,
-	traits::{Get},
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, IterableStorageMap, Parameter,
+	StoragePrefixedMap,
+	traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Get, OnUnbalanced, ReservableCurrency, WithdrawReason,
+		WithdrawReasons,
+	},
+	weights::Weight, error::ModuleErrorMetadata, metadata::DecodeDifferent,
+	storage::{unhashed, migration::{StorageIterator, take_storage_value}},
 };
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_signed, ensure_root};
 use sp_std::prelude::*;
-use sp_runtime::traits::StaticLookup;
+use sp_io::hashing::{blake2_256, twox_64};
+use sp_runtime::{RuntimeDebug, traits::{CheckedSub, StaticLookup, Verify, IdentifyAccount, Zero}};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 
 #[cfg(test)]
 mod mock;
@@ -16,8 +26,138 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+/// Everything stored about a claim, for clients to decode in one go.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClaimRecord<AccountId, BlockNumber> {
+	/// Current owner.
+	pub owner: AccountId,
+	/// Block at which the claim was created or last transferred.
+	pub block_number: BlockNumber,
+	/// Block before which the claim can not be transferred.
+	pub transfer_unlock: Option<BlockNumber>,
+	/// Active lease, as `(lessee, until)`.
+	pub lease: Option<(AccountId, BlockNumber)>,
+	/// Block at which a pending claim becomes active.
+	pub activates_at: Option<BlockNumber>,
+	/// Active lock, ending at the given block or never if `None`.
+	pub lock: Option<Option<BlockNumber>>,
+}
+
+/// Every piece of state stored about a claim, as returned SCALE encoded by `claim_debug`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ClaimDebug<AccountId, BlockNumber, Moment, Balance> {
+	/// See `Proofs`.
+	pub owner: AccountId,
+	/// See `Proofs`.
+	pub block_number: BlockNumber,
+	/// See `ClaimTimestamp`.
+	pub timestamp: Option<Moment>,
+	/// See `TransferUnlock`.
+	pub transfer_unlock: Option<BlockNumber>,
+	/// See `ActivatesAt`, kept after the claim became active.
+	pub activates_at: Option<BlockNumber>,
+	/// See `LastTransferBlock`.
+	pub last_transfer_block: Option<BlockNumber>,
+	/// See `LastProvenBlock`.
+	pub last_proven_block: Option<BlockNumber>,
+	/// See `Leases`, kept after the lease ended.
+	pub lease: Option<(AccountId, BlockNumber)>,
+	/// See `RequiresCosign`.
+	pub cosigner: Option<AccountId>,
+	/// See `CosignedTransfer`.
+	pub cosigned: Option<(AccountId, BlockNumber)>,
+	/// See `TransferAllowlist`.
+	pub transfer_allowlist: Vec<AccountId>,
+	/// See `Challenges`.
+	pub challenge: Option<(AccountId, Balance)>,
+	/// See `TransferRequests`.
+	pub transfer_request: Option<(AccountId, Balance)>,
+	/// See `Renounced`.
+	pub renounced: bool,
+	/// See `Locks`, kept after a timed lock expired.
+	pub lock: Option<Option<BlockNumber>>,
+	/// See `CoOwners`.
+	pub co_owners: Option<(Vec<AccountId>, u16)>,
+	/// See `PendingActions`.
+	pub pending_actions: Vec<PendingAction<AccountId>>,
+	/// See `ScopedApprovals`.
+	pub scoped_approvals: Vec<(AccountId, DelegatePermissions)>,
+	/// See `ClaimUri`.
+	pub uri: Option<Vec<u8>>,
+}
+
+/// Operation on a co-owned claim that runs once enough co-owners approved it, see `CoOwners`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ClaimAction<AccountId> {
+	/// Transfer the claim to the given account.
+	Transfer(AccountId),
+	/// Revoke the claim.
+	Revoke,
+}
+
+/// A proposed `ClaimAction` and the co-owners who approved it so far.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingAction<AccountId> {
+	/// Id passed to `approve_action`.
+	pub id: u32,
+	/// Operation to run.
+	pub action: ClaimAction<AccountId>,
+	/// Co-owners who approved, including the proposer.
+	pub approvals: Vec<AccountId>,
+}
+
+/// What a delegate may do with a claim on behalf of its owner, see `approve_scoped`.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct DelegatePermissions {
+	/// May call `delegated_transfer_claim`.
+	pub can_transfer: bool,
+	/// May call `delegated_revoke_claim`.
+	pub can_revoke: bool,
+}
+
+/// Whether a claim can already be transferred or revoked, see `ActivationDelay`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ClaimStatus {
+	/// Created less than `ActivationDelay` blocks ago.
+	Pending,
+	/// Past its activation delay.
+	Active,
+	/// Not proven under control for longer than `ProofOfControlInterval`.
+	Dormant,
+}
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::NegativeImbalance;
+
+/// Maximum number of results a single claim query returns.
+pub const MAX_QUERY_LIMIT: u32 = 100;
+
+/// Maximum number of `Proofs` entries a single claim query scans.
+pub const MAX_QUERY_SCAN: u32 = 10_000;
+
+/// Maximum number of claims a single `force_revoke_owner_claims` call revokes.
+pub const FORCE_REVOKE_LIMIT: u32 = 100;
+
+/// Storage reads of removing one claim with all of its state, deposits and counters.
+pub const REMOVE_CLAIM_READS: Weight = 13;
+
+/// Storage writes of removing one claim with all of its state, deposits and counters.
+pub const REMOVE_CLAIM_WRITES: Weight = 28;
+
+/// Declared weight of `revoke_claim`.
+pub const REVOKE_CLAIM_WEIGHT: Weight = 50_000;
+
+/// Weight refunded by `revoke_claim` for every byte of claim storage it frees.
+pub const FREED_BYTE_REFUND: Weight = 1_000;
+
 /// The pallet's configuration trait.
-pub trait Trait: system::Trait {
+pub trait Trait: system::Trait + pallet_timestamp::Trait {
 	// Add other types and constants required to configure this pallet.
 
 	/// The overarching event type.
@@ -25,6 +165,81 @@ pub trait Trait: system::Trait {
 
 	// 附加题答案
 	type MaxClaimLength: Get<u32>;
+
+	/// Whether claim operations deposit events; state changes happen either way.
+	type EmitEvents: Get<bool>;
+
+	/// Signature an owner gives a relayer to create a claim on their behalf.
+	type Signature: Verify<Signer = Self::Public> + Parameter;
+
+	/// Public key behind `Signature`, identifying the owner account.
+	type Public: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// Default number of claims an account may own when creating a claim.
+	type MaxClaimsPerOwner: Get<u32>;
+
+	/// Measure UTF-8 claims in characters instead of bytes against `MaxClaimLength`.
+	type CountClaimChars: Get<bool>;
+
+	/// Maximum number of bytes all stored claims may occupy together.
+	type MaxTotalClaimBytes: Get<u64>;
+
+	/// Maximum number of claims an account may bookmark.
+	type MaxBookmarks: Get<u32>;
+
+	/// Only allow transfers to accounts already known to the system pallet.
+	type RequireExistingAccount: Get<bool>;
+
+	/// Minimum number of blocks between two transfers of the same claim.
+	type TransferCooldown: Get<Self::BlockNumber>;
+
+	/// Currency used for challenge bonds.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// Amount reserved from the challenger by `challenge_claim`.
+	type ChallengeBond: Get<BalanceOf<Self>>;
+
+	/// Fee charged to the sender of `transfer_claim`.
+	type TransferFee: Get<BalanceOf<Self>>;
+
+	/// Handler for collected transfer fees.
+	type TransferFeeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+	/// Whether new claims are submitted hex encoded and stored as the decoded bytes.
+	///
+	/// Only creation decodes: every other call takes the claim as stored, i.e. the decoded bytes.
+	type HexClaims: Get<bool>;
+
+	/// Number of blocks a new claim stays pending before it can be transferred or revoked.
+	type ActivationDelay: Get<Self::BlockNumber>;
+
+	/// Blocks after `commit_claim` before the claim can be revealed.
+	type RevealDelay: Get<Self::BlockNumber>;
+
+	/// Blocks after the reveal delay during which a commitment can be revealed.
+	type RevealWindow: Get<Self::BlockNumber>;
+
+	/// Blocks after which a claim whose control was not proven is reported dormant.
+	type ProofOfControlInterval: Get<Self::BlockNumber>;
+
+	/// Amount a recipient holds with `request_claim_transfer`, paid to the owner on approval.
+	type IncomingTransferFee: Get<BalanceOf<Self>>;
+
+	/// Maximum length in bytes of the URI stored by `create_claim_with_uri`.
+	type MaxUriLength: Get<u32>;
+
+	/// Deposit reserved from the caller creating a claim, on top of `PerByteDeposit`.
+	type BaseDeposit: Get<BalanceOf<Self>>;
+
+	/// Deposit reserved from the caller creating a claim per byte of the claim.
+	type PerByteDeposit: Get<BalanceOf<Self>>;
+
+	/// Blocks after which an unanswered `request_claim_transfer` is cancelled and its fee released,
+	/// and for which a `cosign_transfer` approval stays valid.
+	type RequestExpiry: Get<Self::BlockNumber>;
+
+	/// Maximum number of actions pending on a co-owned claim at once.
+	type MaxPendingActions: Get<u32>;
 }
 
 // This pallet's storage items.
@@ -33,15 +248,110 @@ decl_storage! {
 	// storage items are isolated from other pallets.
 	// ---------------------------------vvvvvvvvvvvvvv
 	trait Store for Module<T: Trait> as TemplateModule {
+		/// Claim owner and creation block. `twox_64_concat` is the faster alternative hasher for
+		/// trusted claims; `migrate_proofs_hasher` moves entries stored under it to this one.
 		Proofs get(fn proofs): map hasher(blake2_128_concat) Vec<u8> => (T::AccountId, T::BlockNumber);
+		/// Block before which a claim can not be transferred.
+		TransferUnlock get(fn transfer_unlock): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Number of stored claims per length bucket, see `length_bucket`.
+		ClaimLengthHistogram get(fn claim_length_histogram): map hasher(twox_64_concat) u32 => u32;
+		/// Sum of the byte lengths of all stored claims and their URIs.
+		TotalClaimBytes get(fn total_claim_bytes): u64;
+		/// Number of claims owned by each account.
+		OwnedClaimCount get(fn owned_claim_count): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Per-account override of `MaxClaimsPerOwner`.
+		CreatorQuota get(fn creator_quota): map hasher(blake2_128_concat) T::AccountId => Option<u32>;
+		/// Account that must approve each transfer of a claim.
+		RequiresCosign get(fn cosigner): map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+		/// Destination and last valid block of the transfer a claim's cosigner approved.
+		CosignedTransfer get(fn cosigned_transfer): map hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, T::BlockNumber)>;
+		/// Active or expired lease of a claim, as `(lessee, until)`.
+		Leases get(fn lease): map hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, T::BlockNumber)>;
+		/// Claims each account has bookmarked; bookmarks confer no ownership.
+		Bookmarks get(fn bookmarks): map hasher(blake2_128_concat) T::AccountId => Vec<Vec<u8>>;
+		/// Block of the last `transfer_claim` of each claim.
+		LastTransferBlock get(fn last_transfer_block): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Timestamp of the block a claim was created in.
+		ClaimTimestamp get(fn claim_timestamp): map hasher(blake2_128_concat) Vec<u8> => Option<T::Moment>;
+		/// Open challenge against a claim, as `(challenger, bond)`.
+		Challenges get(fn challenge): map hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Accounts a claim may be transferred to. Empty means unrestricted.
+		TransferAllowlist get(fn transfer_allowlist): map hasher(blake2_128_concat) Vec<u8> => Vec<T::AccountId>;
+		/// Claims owned by each account, the reverse index of `Proofs`.
+		ClaimsOf get(fn claims_of): map hasher(blake2_128_concat) T::AccountId => Vec<Vec<u8>>;
+		/// Block at which a pending claim becomes active.
+		ActivatesAt get(fn activates_at): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Claims given up by their owner with `renounce_claim`.
+		Renounced get(fn renounced): map hasher(blake2_128_concat) Vec<u8> => bool;
+		/// Claim commitments by hash, as `(committer, block committed at)`.
+		Commitments get(fn commitment): map hasher(blake2_128_concat) [u8; 32] => Option<(T::AccountId, T::BlockNumber)>;
+		/// Block at which the owner last called `prove_control` for a claim.
+		LastProvenBlock get(fn last_proven_block): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Pending incoming transfer request of a claim, as `(recipient, held fee)`.
+		TransferRequests get(fn transfer_request): map hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Locked claims, unlocking automatically at the given block or never if `None`.
+		Locks get(fn lock): map hasher(blake2_128_concat) Vec<u8> => Option<Option<T::BlockNumber>>;
+		/// External reference of claims created with `create_claim_with_uri`.
+		ClaimUri get(fn claim_uri): map hasher(blake2_128_concat) Vec<u8> => Option<Vec<u8>>;
+		/// Co-owners of a shared claim and how many of them must approve a transfer or revocation.
+		CoOwners get(fn co_owners): map hasher(blake2_128_concat) Vec<u8> => Option<(Vec<T::AccountId>, u16)>;
+		/// Actions on a co-owned claim still short of their approval threshold.
+		PendingActions get(fn pending_actions): map hasher(blake2_128_concat) Vec<u8> => Vec<PendingAction<T::AccountId>>;
+		/// Id of the next proposed claim action.
+		NextActionId get(fn next_action_id): u32;
+		/// Claims revoked permanently, which can not be created again.
+		Tombstones get(fn tombstoned): map hasher(blake2_128_concat) Vec<u8> => bool;
+		/// Depositor and amount reserved for a claim when it was created.
+		ClaimDeposits get(fn claim_deposit_of): map hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Block at which the transfer request of a claim is cancelled.
+		TransferRequestExpires get(fn transfer_request_expires): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Claims whose transfer request expires at a block. Entries for requests answered or
+		/// replaced in the meantime are skipped.
+		ExpiringRequests: map hasher(twox_64_concat) T::BlockNumber => Vec<Vec<u8>>;
+		/// Delegates of a claim and what each of them may do, cleared when the owner changes.
+		ScopedApprovals get(fn scoped_approvals): map hasher(blake2_128_concat) Vec<u8> => Vec<(T::AccountId, DelegatePermissions)>;
+		/// Number of signed payloads accepted from an account, bound into the next one it signs.
+		SignatureNonces get(fn signature_nonce): map hasher(blake2_128_concat) T::AccountId => u64;
+		/// Last `Proofs` key counted by an unfinished `recompute_counters` run.
+		RecomputeCursor get(fn recompute_cursor): Option<Vec<u8>>;
+		/// Whether `migrate_proofs_hasher` already ran.
+		ProofsHasherMigrated get(fn proofs_hasher_migrated): bool;
 	}
 }
 
 // The pallet's events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
+	pub enum Event<T> where
+		AccountId = <T as system::Trait>::AccountId,
+		BlockNumber = <T as system::Trait>::BlockNumber,
+		Index = <T as system::Trait>::Index,
+	{
 		ClaimCreated(AccountId, Vec<u8>),
 		ClaimRevoked(AccountId, Vec<u8>),
+		ClaimForceTransferred(AccountId, AccountId, Vec<u8>),
+		LeaseGranted(AccountId, AccountId, Vec<u8>, BlockNumber),
+		LeaseRenewed(AccountId, Vec<u8>, BlockNumber),
+		LeaseCancelled(AccountId, Vec<u8>),
+		/// Previous owner, new owner, claim and the new owner's account nonce.
+		ClaimTransferred(AccountId, AccountId, Vec<u8>, Index),
+		ClaimChallenged(AccountId, Vec<u8>),
+		ChallengeUpheld(AccountId, Vec<u8>),
+		ChallengeDismissed(AccountId, Vec<u8>),
+		CountersRecomputed,
+		ClaimRenounced(AccountId, Vec<u8>),
+		ControlProven(AccountId, Vec<u8>, u64),
+		ClaimCreationRejected(Vec<u8>, AccountId),
+		TransferRequested(AccountId, Vec<u8>),
+		TransferRequestRejected(AccountId, Vec<u8>),
+		ClaimLocked(AccountId, Vec<u8>, Option<BlockNumber>),
+		ClaimUnlocked(AccountId, Vec<u8>),
+		CoOwnersSet(AccountId, Vec<u8>, u16),
+		ActionProposed(AccountId, Vec<u8>, u32),
+		ActionApproved(AccountId, Vec<u8>, u32),
+		ActionExecuted(Vec<u8>, u32),
+		OwnerClaimsRevoked(AccountId, u32),
+		TransferRequestExpired(AccountId, Vec<u8>),
+		ScopedApprovalSet(AccountId, Vec<u8>, AccountId, DelegatePermissions),
 	}
 );
 
@@ -52,6 +362,51 @@ decl_error! {
 		ClaimNotExist,
 		NotClaimOwner,
 		ProofTooLong,
+		TransferTimelocked,
+		InvalidSignature,
+		TooManyClaims,
+		CosignRequired,
+		CosignerAlreadySet,
+		NotCosigner,
+		StorageBudgetExceeded,
+		ClaimLeased,
+		NotLessee,
+		InvalidLeaseEnd,
+		ClaimNotLeased,
+		TooManyBookmarks,
+		AlreadyBookmarked,
+		NotBookmarked,
+		DestinationNotFound,
+		TransferTooSoon,
+		ClaimChallenged,
+		AlreadyChallenged,
+		NotChallenged,
+		DestinationNotAllowed,
+		InsufficientBalance,
+		InvalidClaimFormat,
+		InvalidConsent,
+		ClaimPending,
+		ClaimRenounced,
+		AlreadyCommitted,
+		CommitmentNotFound,
+		NotCommitter,
+		RevealTooEarly,
+		CommitmentExpired,
+		TransferAlreadyRequested,
+		NoTransferRequest,
+		ClaimLocked,
+		ClaimNotLocked,
+		InvalidUri,
+		UriTooLong,
+		ClaimCoOwned,
+		AlreadyCoOwned,
+		InvalidThreshold,
+		NotCoOwner,
+		ActionNotFound,
+		AlreadyApproved,
+		ClaimTombstoned,
+		NotPermitted,
+		TooManyPendingActions,
 	}
 }
 
@@ -68,24 +423,108 @@ decl_module! {
 		// this is needed only if you are using events in your pallet
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			if Self::proofs_hasher_migrated() {
+				return T::DbWeight::get().reads(1);
+			}
+			ProofsHasherMigrated::put(true);
+
+			Self::migrate_proofs_hasher().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::expire_transfer_requests(now)
+		}
+
 		#[weight = 0]
 		pub fn create_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
+			let claim = Self::normalize_claim(claim)?;
+
+			if Proofs::<T>::contains_key(&claim) {
+				// Tell the caller who holds the claim; the event outlives the failed call.
+				let (owner, _block_number) = Proofs::<T>::get(&claim);
+				Self::emit_event(RawEvent::ClaimCreationRejected(claim, owner));
+				return Err(Error::<T>::ProofAlreadyExist.into());
+			}
+
+			Self::ensure_can_create(&claim)?;
+			Self::ensure_within_quota(&sender)?;
+
+			Self::reserve_deposit(&sender, &claim)?;
+			Self::insert_claim(&sender, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		/// Create a claim owned by `owner`, submitted by a relayer holding `owner`'s signature over
+		/// `create_claim_payload`.
+		#[weight = 0]
+		pub fn create_claim_signed(origin, claim: Vec<u8>, owner: T::AccountId, signature: T::Signature) -> dispatch::DispatchResult {
+			let relayer = ensure_signed(origin)?;
+
+			let payload = Self::create_claim_payload(&claim, &owner);
+			ensure!(signature.verify(&payload[..], &owner), Error::<T>::InvalidSignature);
 
-			// 附加题答案
-			ensure!(T::MaxClaimLength::get() >= claim.len() as u32, Error::<T>::ProofTooLong);
+			let claim = Self::normalize_claim(claim)?;
 
-			Proofs::<T>::insert(&claim, (sender.clone(), system::Module::<T>::block_number()));
+			Self::ensure_can_create(&claim)?;
+			Self::ensure_within_quota(&owner)?;
 
-			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+			Self::reserve_deposit(&relayer, &claim)?;
+			SignatureNonces::<T>::mutate(&owner, |nonce| *nonce = nonce.wrapping_add(1));
+			Self::insert_claim(&owner, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(owner, claim));
 
 			Ok(())
 		}
 
+		/// Revoke a claim, refunding part of the weight for the storage it frees. A `permanent`
+		/// revocation leaves a tombstone so the claim can never be created again.
+		#[weight = REVOKE_CLAIM_WEIGHT]
+		pub fn revoke_claim(origin, claim: Vec<u8>, permanent: bool) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::ClaimCoOwned);
+
+			Self::do_revoke(sender, claim.clone())?;
+
+			if permanent {
+				Tombstones::insert(&claim, true);
+				return Ok(None.into());
+			}
+
+			let refund = FREED_BYTE_REFUND.saturating_mul(claim.len() as Weight);
+
+			Ok(Some(REVOKE_CLAIM_WEIGHT.saturating_sub(refund)).into())
+		}
+
+		// 第二题答案
+		/// Transfer a claim to `dest`. An active lease stays in place with `carry_lease`,
+		/// otherwise it is cancelled.
+		#[weight = 0]
+		pub fn transfer_claim(
+			origin,
+			claim: Vec<u8>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			carry_lease: bool,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let dest = T::Lookup::lookup(dest)?;
+
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::ClaimCoOwned);
+
+			Self::do_transfer(sender, claim, dest, carry_lease)
+		}
+
+		/// Freeze transfers of a claim until the given block.
 		#[weight = 0]
-		pub fn revoke_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+		pub fn set_transfer_unlock(origin, claim: Vec<u8>, unlock_at: T::BlockNumber) -> dispatch::DispatchResult {
 			let sender = ensure_signed(origin)?;
 
 			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
@@ -94,16 +533,80 @@ decl_module! {
 
 			ensure!(owner == sender, Error::<T>::NotClaimOwner);
 
-			Proofs::<T>::remove(&claim);
+			TransferUnlock::<T>::insert(&claim, unlock_at);
+
+			Ok(())
+		}
+
+		/// Reassign a claim to a new owner, ignoring any transfer restrictions. Leases, locks,
+		/// challenges and a renouncement end with the move; a challenger gets their bond back.
+		#[weight = 0]
+		pub fn force_transfer_claim(origin, claim: Vec<u8>, new_owner: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			let new_owner = T::Lookup::lookup(new_owner)?;
 
-			Self::deposit_event(RawEvent::ClaimRevoked(sender, claim));
+			if Self::active_lease(&claim).is_some() {
+				Self::emit_event(RawEvent::LeaseCancelled(owner.clone(), claim.clone()));
+			}
+			if let Some((challenger, bond)) = Challenges::<T>::take(&claim) {
+				T::Currency::unreserve(&challenger, bond);
+			}
+
+			Self::change_owner(&claim, &owner, &new_owner);
+			Self::cancel_transfer_request(&claim);
+			TransferUnlock::<T>::remove(&claim);
+			TransferAllowlist::<T>::remove(&claim);
+			CosignedTransfer::<T>::remove(&claim);
+			Leases::<T>::remove(&claim);
+			Locks::<T>::remove(&claim);
+			Renounced::remove(&claim);
+			LastTransferBlock::<T>::insert(&claim, system::Module::<T>::block_number());
+
+			Self::emit_event(RawEvent::ClaimForceTransferred(owner, new_owner, claim));
 
 			Ok(())
 		}
 
-		// 第二题答案
+		/// Revoke `old_claim` and create `new_claim` for the same owner in one call.
+		#[weight = 0]
+		pub fn replace_claim(origin, old_claim: Vec<u8>, new_claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!CoOwners::<T>::contains_key(&old_claim), Error::<T>::ClaimCoOwned);
+			Self::ensure_can_revoke(&sender, &old_claim)?;
+
+			// Validate the new claim before touching storage so a failure loses nothing.
+			let new_claim = Self::normalize_claim(new_claim)?;
+			Self::ensure_can_create(&new_claim)?;
+			Self::reserve_deposit(&sender, &new_claim)?;
+
+			Self::remove_claim(&old_claim);
+			Self::insert_claim(&sender, &new_claim);
+
+			Self::emit_event(RawEvent::ClaimRevoked(sender.clone(), old_claim));
+			Self::emit_event(RawEvent::ClaimCreated(sender, new_claim));
+
+			Ok(())
+		}
+
+		/// Override how many claims `who` may own when creating claims, `None` restores the default.
+		#[weight = 0]
+		pub fn set_quota(origin, who: T::AccountId, quota: Option<u32>) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			CreatorQuota::<T>::mutate_exists(&who, |q| *q = quota);
+
+			Ok(())
+		}
+
+		/// Require `cosigner` to approve every future transfer of a claim.
 		#[weight = 0]
-		pub fn transfer_claim(origin, claim: Vec<u8>, dest: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+		pub fn require_cosign(origin, claim: Vec<u8>, cosigner: T::AccountId) -> dispatch::DispatchResult {
 			let sender = ensure_signed(origin)?;
 
 			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
@@ -112,11 +615,1347 @@ decl_module! {
 
 			ensure!(owner == sender, Error::<T>::NotClaimOwner);
 
+			ensure!(!RequiresCosign::<T>::contains_key(&claim), Error::<T>::CosignerAlreadySet);
+
+			RequiresCosign::<T>::insert(&claim, cosigner);
+
+			Ok(())
+		}
+
+		/// Approve the next transfer of a claim to `dest` as its cosigner, for `RequestExpiry` blocks.
+		#[weight = 0]
+		pub fn cosign_transfer(origin, claim: Vec<u8>, dest: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
 
-			Proofs::<T>::insert(&claim, (dest, system::Module::<T>::block_number()));
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			ensure!(Self::cosigner(&claim) == Some(sender), Error::<T>::NotCosigner);
+
+			let until = system::Module::<T>::block_number() + T::RequestExpiry::get();
+			CosignedTransfer::<T>::insert(&claim, (dest, until));
+
+			Ok(())
+		}
+
+		/// Lease a claim to `lessee` until the given block, after which control reverts to the owner.
+		#[weight = 0]
+		pub fn lease_claim(origin, claim: Vec<u8>, lessee: T::AccountId, until: T::BlockNumber) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			ensure!(Self::active_lease(&claim).is_none(), Error::<T>::ClaimLeased);
+
+			ensure!(until > system::Module::<T>::block_number(), Error::<T>::InvalidLeaseEnd);
+
+			Leases::<T>::insert(&claim, (lessee.clone(), until));
+
+			Self::emit_event(RawEvent::LeaseGranted(sender, lessee, claim, until));
+
+			Ok(())
+		}
+
+		/// Extend an active lease as its lessee.
+		#[weight = 0]
+		pub fn renew_lease(origin, claim: Vec<u8>, until: T::BlockNumber) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let (lessee, current_until) = Self::active_lease(&claim).ok_or(Error::<T>::NotLessee)?;
+
+			ensure!(lessee == sender, Error::<T>::NotLessee);
+
+			ensure!(until > current_until, Error::<T>::InvalidLeaseEnd);
+
+			Leases::<T>::insert(&claim, (lessee, until));
+
+			Self::emit_event(RawEvent::LeaseRenewed(sender, claim, until));
+
+			Ok(())
+		}
+
+		/// End an active lease early as the claim owner.
+		#[weight = 0]
+		pub fn cancel_lease(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			ensure!(Self::active_lease(&claim).is_some(), Error::<T>::ClaimNotLeased);
+
+			Leases::<T>::remove(&claim);
+
+			Self::emit_event(RawEvent::LeaseCancelled(sender, claim));
+
+			Ok(())
+		}
+
+		/// Bookmark a claim to watch it.
+		#[weight = 0]
+		pub fn bookmark_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let mut bookmarks = Self::bookmarks(&sender);
+
+			ensure!(!bookmarks.contains(&claim), Error::<T>::AlreadyBookmarked);
+			ensure!((bookmarks.len() as u32) < T::MaxBookmarks::get(), Error::<T>::TooManyBookmarks);
+
+			bookmarks.push(claim);
+			Bookmarks::<T>::insert(&sender, bookmarks);
+
+			Ok(())
+		}
+
+		/// Remove a claim from the caller's bookmarks.
+		#[weight = 0]
+		pub fn unbookmark_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut bookmarks = Self::bookmarks(&sender);
+
+			let index = bookmarks.iter().position(|c| *c == claim).ok_or(Error::<T>::NotBookmarked)?;
+
+			bookmarks.swap_remove(index);
+			if bookmarks.is_empty() {
+				Bookmarks::<T>::remove(&sender);
+			} else {
+				Bookmarks::<T>::insert(&sender, bookmarks);
+			}
+
+			Ok(())
+		}
+
+		/// Create a claim stored under the id derived from the caller and `content`.
+		///
+		/// Derived ids have a fixed length, so `MaxClaimLength` does not apply to them.
+		#[weight = 0]
+		pub fn create_derived_claim(origin, content: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let claim = Self::derive_claim_id(&sender, &content).to_vec();
+
+			Self::ensure_can_store(&claim)?;
+			Self::ensure_within_quota(&sender)?;
+
+			Self::reserve_deposit(&sender, &claim)?;
+			Self::insert_claim(&sender, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		/// Create a claim owned by `owner` on their behalf.
+		#[weight = 0]
+		pub fn create_claim_for(origin, claim: Vec<u8>, owner: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			let claim = Self::normalize_claim(claim)?;
+			Self::ensure_can_create(&claim)?;
+
+			let owner = T::Lookup::lookup(owner)?;
+
+			Self::insert_claim(&owner, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(owner, claim));
+
+			Ok(())
+		}
+
+		/// Revoke a derived claim by its 32 byte id, see `create_derived_claim`.
+		#[weight = REVOKE_CLAIM_WEIGHT]
+		pub fn revoke_claim_by_hash(origin, hash: [u8; 32]) -> dispatch::DispatchResultWithPostInfo {
+			Self::revoke_claim(origin, hash.to_vec(), false)
+		}
+
+		/// Dispute a claim, reserving `ChallengeBond` from the challenger.
+		#[weight = 0]
+		pub fn challenge_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+			ensure!(!Challenges::<T>::contains_key(&claim), Error::<T>::AlreadyChallenged);
+
+			let bond = T::ChallengeBond::get();
+			T::Currency::reserve(&sender, bond)?;
+			Challenges::<T>::insert(&claim, (sender.clone(), bond));
+
+			Self::emit_event(RawEvent::ClaimChallenged(sender, claim));
+
+			Ok(())
+		}
+
+		/// Settle a challenge. Upholding revokes the claim and returns the bond,
+		/// dismissing slashes the bond.
+		#[weight = 0]
+		pub fn resolve_challenge(origin, claim: Vec<u8>, uphold: bool) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			let (challenger, bond) = Self::challenge(&claim).ok_or(Error::<T>::NotChallenged)?;
+			Challenges::<T>::remove(&claim);
+
+			if uphold {
+				T::Currency::unreserve(&challenger, bond);
+				Self::remove_claim(&claim);
+				Self::emit_event(RawEvent::ChallengeUpheld(challenger, claim));
+			} else {
+				let _ = T::Currency::slash_reserved(&challenger, bond);
+				Self::emit_event(RawEvent::ChallengeDismissed(challenger, claim));
+			}
+
+			Ok(())
+		}
+
+		/// Restrict transfers of a claim to `allowed`. An empty list lifts the restriction.
+		#[weight = 0]
+		pub fn set_transfer_allowlist(origin, claim: Vec<u8>, allowed: Vec<T::AccountId>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			if allowed.is_empty() {
+				TransferAllowlist::<T>::remove(&claim);
+			} else {
+				TransferAllowlist::<T>::insert(&claim, allowed);
+			}
+
+			Ok(())
+		}
+
+		/// Create a claim for the sender unless they already own it.
+		#[weight = 0]
+		pub fn ensure_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let claim = Self::normalize_claim(claim)?;
+
+			if Proofs::<T>::contains_key(&claim) {
+				let (owner, _block_number) = Proofs::<T>::get(&claim);
+				ensure!(owner == sender, Error::<T>::NotClaimOwner);
+				return Ok(());
+			}
+
+			Self::ensure_can_create(&claim)?;
+			Self::ensure_within_quota(&sender)?;
+
+			Self::reserve_deposit(&sender, &claim)?;
+			Self::insert_claim(&sender, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		/// Rebuild `OwnedClaimCount`, `ClaimsOf`, `ClaimLengthHistogram` and `TotalClaimBytes` from `Proofs`.
+		///
+		/// Counts at most `MAX_QUERY_LIMIT` claims per call; call again until `CountersRecomputed` is emitted.
+		#[weight = T::DbWeight::get().reads_writes(1 + 5 * MAX_QUERY_LIMIT as Weight, 5 + 4 * MAX_QUERY_LIMIT as Weight)]
+		pub fn recompute_counters(origin) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			let cursor = Self::recompute_cursor();
+			if cursor.is_none() {
+				OwnedClaimCount::<T>::remove_all();
+				ClaimsOf::<T>::remove_all();
+				ClaimLengthHistogram::remove_all();
+				TotalClaimBytes::kill();
+			}
+
+			let (claims, next) = Self::all_claims(cursor, MAX_QUERY_LIMIT);
+			for (claim, owner, _block_number) in claims {
+				OwnedClaimCount::<T>::mutate(&owner, |count| *count = count.saturating_add(1));
+				ClaimsOf::<T>::mutate(&owner, |claims| claims.push(claim.clone()));
+				Self::note_claim_added(&claim);
+			}
+
+			match next {
+				Some(key) => RecomputeCursor::put(key),
+				None => {
+					RecomputeCursor::kill();
+					Self::emit_event(RawEvent::CountersRecomputed);
+				}
+			}
 
 			Ok(())
 		}
+
+		/// Transfer a claim to `dest`, who consents by signing `consent_payload`.
+		#[weight = 0]
+		pub fn transfer_claim_with_consent(
+			origin,
+			claim: Vec<u8>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			dest_signature: T::Signature,
+		) -> dispatch::DispatchResult {
+			let recipient = T::Lookup::lookup(dest.clone())?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+			let payload = Self::consent_payload(&claim, &owner, &recipient);
+
+			ensure!(dest_signature.verify(&payload[..], &recipient), Error::<T>::InvalidConsent);
+
+			Self::transfer_claim(origin, claim, dest, false)?;
+
+			SignatureNonces::<T>::mutate(&recipient, |nonce| *nonce = nonce.wrapping_add(1));
+
+			Ok(())
+		}
+
+		/// Transfer a claim to the `threshold` multisig account of `signatories`.
+		#[weight = 0]
+		pub fn transfer_claim_to_multisig(
+			origin,
+			claim: Vec<u8>,
+			signatories: Vec<T::AccountId>,
+			threshold: u16,
+		) -> dispatch::DispatchResult {
+			let mut unique = signatories.clone();
+			unique.sort();
+			unique.dedup();
+
+			ensure!(threshold > 0 && threshold as usize <= unique.len(), Error::<T>::InvalidThreshold);
+
+			let multisig = Self::multisig_account_id(&signatories, threshold);
+
+			Self::transfer_claim(origin, claim, T::Lookup::unlookup(multisig), false)
+		}
+
+		/// Give up a claim for good. It stays on record, owned by the default account, and can
+		/// no longer be transferred or revoked. Its deposit is returned.
+		#[weight = 0]
+		pub fn renounce_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			ensure!(Self::active_lease(&claim).is_none(), Error::<T>::ClaimLeased);
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::ClaimCoOwned);
+
+			Self::change_owner(&claim, &owner, &T::AccountId::default());
+			Renounced::insert(&claim, true);
+			Self::release_deposit(&claim);
+
+			Self::emit_event(RawEvent::ClaimRenounced(owner, claim));
+
+			Ok(())
+		}
+
+		/// Commit to `blake2_256(claim ++ salt)` ahead of revealing the claim.
+		#[weight = 0]
+		pub fn commit_claim(origin, hash: [u8; 32]) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let now = system::Module::<T>::block_number();
+			if let Some((_, committed_at)) = Self::commitment(&hash) {
+				ensure!(now > Self::reveal_deadline(committed_at), Error::<T>::AlreadyCommitted);
+			}
+
+			Commitments::<T>::insert(&hash, (sender, now));
+
+			Ok(())
+		}
+
+		/// Create a claim committed to earlier with `commit_claim`.
+		///
+		/// The commitment covers the claim as submitted, before `normalize_claim` decodes it.
+		#[weight = 0]
+		pub fn reveal_claim(origin, claim: Vec<u8>, salt: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let hash = blake2_256(&[&claim[..], &salt[..]].concat());
+			let (committer, committed_at) = Self::commitment(&hash).ok_or(Error::<T>::CommitmentNotFound)?;
+
+			ensure!(committer == sender, Error::<T>::NotCommitter);
+
+			let now = system::Module::<T>::block_number();
+			ensure!(now >= committed_at + T::RevealDelay::get(), Error::<T>::RevealTooEarly);
+			ensure!(now <= Self::reveal_deadline(committed_at), Error::<T>::CommitmentExpired);
+
+			let claim = Self::normalize_claim(claim)?;
+			Self::ensure_can_create(&claim)?;
+			Self::ensure_within_quota(&sender)?;
+
+			Self::reserve_deposit(&sender, &claim)?;
+			Commitments::<T>::remove(&hash);
+			Self::insert_claim(&sender, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		/// Show that the owner still controls a claim. `nonce` is echoed in the event.
+		#[weight = 0]
+		pub fn prove_control(origin, claim: Vec<u8>, nonce: u64) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			LastProvenBlock::<T>::insert(&claim, system::Module::<T>::block_number());
+
+			Self::emit_event(RawEvent::ControlProven(sender, claim, nonce));
+
+			Ok(())
+		}
+
+		/// Ask the owner `from` to transfer a claim to the caller, holding `IncomingTransferFee`.
+		#[weight = 0]
+		pub fn request_claim_transfer(origin, claim: Vec<u8>, from: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+			let from = T::Lookup::lookup(from)?;
+
+			ensure!(owner == from, Error::<T>::NotClaimOwner);
+			ensure!(!TransferRequests::<T>::contains_key(&claim), Error::<T>::TransferAlreadyRequested);
+
+			let fee = T::IncomingTransferFee::get();
+			T::Currency::reserve(&sender, fee).map_err(|_| Error::<T>::InsufficientBalance)?;
+			TransferRequests::<T>::insert(&claim, (sender.clone(), fee));
+
+			let expires = system::Module::<T>::block_number() + T::RequestExpiry::get();
+			TransferRequestExpires::<T>::insert(&claim, expires);
+			ExpiringRequests::<T>::mutate(expires, |claims| claims.push(claim.clone()));
+
+			Self::emit_event(RawEvent::TransferRequested(sender, claim));
+
+			Ok(())
+		}
+
+		/// Transfer a claim to the account that requested it, collecting the held fee.
+		#[weight = 0]
+		pub fn approve_incoming_transfer(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let (recipient, fee) = Self::transfer_request(&claim).ok_or(Error::<T>::NoTransferRequest)?;
+
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::ClaimCoOwned);
+			// Also checks the owner can pay `TransferFee`, so `do_transfer` can not fail below.
+			Self::ensure_can_transfer(&sender, &claim, &recipient)?;
+
+			T::Currency::repatriate_reserved(&recipient, &sender, fee, BalanceStatus::Free)?;
+			Self::do_transfer(sender, claim.clone(), recipient, false)?;
+
+			TransferRequests::<T>::remove(&claim);
+			TransferRequestExpires::<T>::remove(&claim);
+
+			Ok(())
+		}
+
+		/// Turn down a transfer request, returning the held fee to the requester.
+		#[weight = 0]
+		pub fn reject_incoming_transfer(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			let (recipient, fee) = Self::transfer_request(&claim).ok_or(Error::<T>::NoTransferRequest)?;
+
+			TransferRequests::<T>::remove(&claim);
+			TransferRequestExpires::<T>::remove(&claim);
+			T::Currency::unreserve(&recipient, fee);
+
+			Self::emit_event(RawEvent::TransferRequestRejected(recipient, claim));
+
+			Ok(())
+		}
+
+		/// Block transfers and revocation of a claim until `until`, or until `unlock_claim` if `None`.
+		#[weight = 0]
+		pub fn lock_claim(origin, claim: Vec<u8>, until: Option<T::BlockNumber>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			Locks::<T>::insert(&claim, until);
+
+			Self::emit_event(RawEvent::ClaimLocked(sender, claim, until));
+
+			Ok(())
+		}
+
+		/// Lift the lock of a claim before it expires.
+		#[weight = 0]
+		pub fn unlock_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(Self::is_locked(&claim), Error::<T>::ClaimNotLocked);
+
+			Locks::<T>::remove(&claim);
+
+			Self::emit_event(RawEvent::ClaimUnlocked(sender, claim));
+
+			Ok(())
+		}
+
+		/// Create a claim pointing at off-chain content by an `http`, `https` or `ipfs` URI.
+		#[weight = 0]
+		pub fn create_claim_with_uri(origin, claim: Vec<u8>, uri: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let claim = Self::normalize_claim(claim)?;
+
+			ensure!(uri.len() as u32 <= T::MaxUriLength::get(), Error::<T>::UriTooLong);
+			ensure!(Self::has_valid_scheme(&uri), Error::<T>::InvalidUri);
+
+			Self::ensure_can_create(&claim)?;
+			Self::ensure_within_quota(&sender)?;
+
+			let total = Self::total_claim_bytes().saturating_add((claim.len() + uri.len()) as u64);
+			ensure!(total <= T::MaxTotalClaimBytes::get(), Error::<T>::StorageBudgetExceeded);
+
+			Self::reserve_deposit(&sender, &claim)?;
+			// Stored first so `insert_claim` counts its bytes.
+			ClaimUri::insert(&claim, uri);
+			Self::insert_claim(&sender, &claim);
+
+			Self::emit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		/// Share a claim with `owners`. From then on it is only transferred or revoked through
+		/// actions approved by `threshold` of them.
+		#[weight = 0]
+		pub fn set_co_owners(origin, claim: Vec<u8>, owners: Vec<T::AccountId>, threshold: u16) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::AlreadyCoOwned);
+
+			let mut owners = owners;
+			owners.sort();
+			owners.dedup();
+
+			ensure!(threshold > 0 && threshold as usize <= owners.len(), Error::<T>::InvalidThreshold);
+
+			CoOwners::<T>::insert(&claim, (owners, threshold));
+
+			Self::emit_event(RawEvent::CoOwnersSet(sender, claim, threshold));
+
+			Ok(())
+		}
+
+		/// Propose an action on a co-owned claim, counting as the first approval.
+		#[weight = 0]
+		pub fn propose_action(origin, claim: Vec<u8>, action: ClaimAction<T::AccountId>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let (owners, _threshold) = Self::co_owners(&claim).ok_or(Error::<T>::NotCoOwner)?;
+
+			ensure!(owners.contains(&sender), Error::<T>::NotCoOwner);
+
+			let mut actions = Self::pending_actions(&claim);
+			ensure!((actions.len() as u32) < T::MaxPendingActions::get(), Error::<T>::TooManyPendingActions);
+
+			let id = Self::next_action_id();
+			let pending = PendingAction { id, action, approvals: Vec::new() };
+
+			NextActionId::put(id.wrapping_add(1));
+			actions.push(pending);
+			PendingActions::<T>::insert(&claim, actions);
+
+			Self::emit_event(RawEvent::ActionProposed(sender.clone(), claim.clone(), id));
+
+			Self::do_approve(sender, claim, id)
+		}
+
+		/// Approve a pending action on a co-owned claim, running it once the threshold is reached.
+		#[weight = 0]
+		pub fn approve_action(origin, claim: Vec<u8>, action_id: u32) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::do_approve(sender, claim, action_id)
+		}
+
+		/// Revoke up to `FORCE_REVOKE_LIMIT` claims of `owner`, refunding their deposits. Call
+		/// again while `owner` still holds claims. Only the claims actually revoked are charged.
+		#[weight = T::DbWeight::get().reads_writes(
+			1 + REMOVE_CLAIM_READS * FORCE_REVOKE_LIMIT as Weight,
+			1 + REMOVE_CLAIM_WRITES * FORCE_REVOKE_LIMIT as Weight,
+		)]
+		pub fn force_revoke_owner_claims(
+			origin,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> dispatch::DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let owner = T::Lookup::lookup(owner)?;
+
+			let claims = Self::claims_of(&owner);
+			let count = claims.len().min(FORCE_REVOKE_LIMIT as usize);
+
+			for claim in claims.iter().take(count) {
+				Self::remove_claim(claim);
+			}
+
+			Self::emit_event(RawEvent::OwnerClaimsRevoked(owner, count as u32));
+
+			let removed = count as Weight;
+			let weight = T::DbWeight::get().reads_writes(
+				1 + REMOVE_CLAIM_READS * removed,
+				1 + REMOVE_CLAIM_WRITES * removed,
+			);
+
+			Ok(Some(weight).into())
+		}
+
+		/// Let `delegate` transfer and/or revoke a claim. Granting neither removes the delegate.
+		#[weight = 0]
+		pub fn approve_scoped(
+			origin,
+			claim: Vec<u8>,
+			delegate: T::AccountId,
+			can_transfer: bool,
+			can_revoke: bool,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			let permissions = DelegatePermissions { can_transfer, can_revoke };
+			let mut approvals = Self::scoped_approvals(&claim);
+			approvals.retain(|(account, _)| *account != delegate);
+			if can_transfer || can_revoke {
+				approvals.push((delegate.clone(), permissions));
+			}
+			if approvals.is_empty() {
+				ScopedApprovals::<T>::remove(&claim);
+			} else {
+				ScopedApprovals::<T>::insert(&claim, approvals);
+			}
+
+			Self::emit_event(RawEvent::ScopedApprovalSet(sender, claim, delegate, permissions));
+
+			Ok(())
+		}
+
+		/// Transfer a claim on behalf of its owner as a delegate allowed to transfer it.
+		#[weight = 0]
+		pub fn delegated_transfer_claim(origin, claim: Vec<u8>, dest: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let dest = T::Lookup::lookup(dest)?;
+
+			ensure!(Self::delegate_permissions(&claim, &sender).can_transfer, Error::<T>::NotPermitted);
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::ClaimCoOwned);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			Self::do_transfer(owner, claim, dest, false)
+		}
+
+		/// Revoke a claim on behalf of its owner as a delegate allowed to revoke it.
+		#[weight = 0]
+		pub fn delegated_revoke_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::delegate_permissions(&claim, &sender).can_revoke, Error::<T>::NotPermitted);
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::ClaimCoOwned);
+
+			let (owner, _block_number) = Proofs::<T>::get(&claim);
+
+			Self::do_revoke(owner, claim)
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn emit_event(event: Event<T>) {
+		if T::EmitEvents::get() {
+			Self::deposit_event(event);
+		}
+	}
+
+	/// Bucket of a claim length: bucket `n` holds lengths in `[2^(n-1), 2^n)`, bucket 0 the empty claim.
+	pub fn length_bucket(len: usize) -> u32 {
+		32 - (len as u32).leading_zeros()
+	}
+
+	/// All non-empty histogram buckets, as `(bucket, count)` pairs.
+	pub fn claim_length_buckets() -> Vec<(u32, u32)> {
+		let mut buckets: Vec<(u32, u32)> = ClaimLengthHistogram::iter().collect();
+		buckets.sort();
+		buckets
+	}
+
+	/// Up to `limit` claims starting with `prefix`, see `MAX_QUERY_LIMIT` and `MAX_QUERY_SCAN`.
+	pub fn claims_with_prefix(prefix: Vec<u8>, limit: u32) -> Vec<Vec<u8>> {
+		Proofs::<T>::iter()
+			.take(MAX_QUERY_SCAN as usize)
+			.map(|(claim, _)| claim)
+			.filter(|claim| claim.starts_with(&prefix))
+			.take(limit.min(MAX_QUERY_LIMIT) as usize)
+			.collect()
+	}
+
+	/// Up to `limit` claims with their owner and block number, in storage key order, starting
+	/// after the raw storage key `start_key`. Also returns the cursor for the next page, if any.
+	///
+	/// A `limit` of 0 or above `MAX_QUERY_LIMIT` returns a page of `MAX_QUERY_LIMIT` claims, and
+	/// a `start_key` outside of `Proofs` is ignored, starting from the first claim.
+	pub fn all_claims(
+		start_key: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<(Vec<u8>, T::AccountId, T::BlockNumber)>, Option<Vec<u8>>) {
+		let prefix = Proofs::<T>::final_prefix().to_vec();
+		let limit = if limit == 0 { MAX_QUERY_LIMIT } else { limit.min(MAX_QUERY_LIMIT) } as usize;
+		let mut claims = Vec::new();
+		let mut key = start_key.filter(|key| key.starts_with(&prefix)).unwrap_or_else(|| prefix.clone());
+
+		while let Some(next) = sp_io::storage::next_key(&key) {
+			if !next.starts_with(&prefix) {
+				break;
+			}
+			if claims.len() == limit {
+				return (claims, Some(key));
+			}
+			// Keys are `blake2_128_concat` hashed: 16 bytes of hash followed by the encoded claim.
+			let claim = next.get(prefix.len() + 16..).and_then(|mut raw| Vec::<u8>::decode(&mut raw).ok());
+			let record = unhashed::get::<(T::AccountId, T::BlockNumber)>(&next);
+			if let (Some(claim), Some((owner, block_number))) = (claim, record) {
+				claims.push((claim, owner, block_number));
+			}
+			key = next;
+		}
+
+		(claims, None)
+	}
+
+	/// Number of claims held by `owner` and the oldest and newest of their block numbers.
+	pub fn owner_stats(owner: &T::AccountId) -> (u32, Option<T::BlockNumber>, Option<T::BlockNumber>) {
+		let block_numbers: Vec<T::BlockNumber> = Self::claims_of(owner).iter()
+			.map(|claim| Proofs::<T>::get(claim).1)
+			.collect();
+
+		(
+			block_numbers.len() as u32,
+			block_numbers.iter().min().cloned(),
+			block_numbers.iter().max().cloned(),
+		)
+	}
+
+	/// Index and name of every `Error` variant, as found in a module dispatch error.
+	pub fn error_names() -> Vec<(u8, Vec<u8>)> {
+		<Error<T> as ModuleErrorMetadata>::metadata().iter()
+			.enumerate()
+			.filter_map(|(index, error)| match error.name {
+				DecodeDifferent::Encode(name) => Some((index as u8, name.as_bytes().to_vec())),
+				DecodeDifferent::Decoded(_) => None,
+			})
+			.collect()
+	}
+
+	/// Account id of the `threshold` multisig of `signatories`, derived like pallet-multisig does.
+	pub fn multisig_account_id(signatories: &[T::AccountId], threshold: u16) -> T::AccountId {
+		let mut signatories = signatories.to_vec();
+		signatories.sort();
+		signatories.dedup();
+		let entropy = (b"modlpy/utilisuba", signatories, threshold).using_encoded(blake2_256);
+		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+	}
+
+	/// Message `owner` signs to have `claim` created by `create_claim_signed`, valid once.
+	pub fn create_claim_payload(claim: &[u8], owner: &T::AccountId) -> Vec<u8> {
+		(b"poe/create", claim, owner, Self::signature_nonce(owner)).encode()
+	}
+
+	/// Message `dest` signs to accept `claim` from `owner` with `transfer_claim_with_consent`, valid once.
+	pub fn consent_payload(claim: &[u8], owner: &T::AccountId, dest: &T::AccountId) -> Vec<u8> {
+		(b"poe/consent", claim, owner, dest, Self::signature_nonce(dest)).encode()
+	}
+
+	/// Rehash `Proofs` entries stored under `twox_64_concat` keys into `blake2_128_concat` keys,
+	/// returning the weight consumed. Entries already using the new hasher are left alone.
+	pub fn migrate_proofs_hasher() -> Weight {
+		let entries: Vec<(Vec<u8>, (T::AccountId, T::BlockNumber))> =
+			StorageIterator::<(T::AccountId, T::BlockNumber)>::new(b"TemplateModule", b"Proofs").collect();
+		let mut migrated: Weight = 0;
+		for (key, record) in &entries {
+			if key.len() < 8 || key[..8] != twox_64(&key[8..]) {
+				continue;
+			}
+			let claim = match Vec::<u8>::decode(&mut &key[8..]) {
+				Ok(claim) => claim,
+				Err(_) => continue,
+			};
+			take_storage_value::<(T::AccountId, T::BlockNumber)>(b"TemplateModule", b"Proofs", key);
+			Proofs::<T>::insert(claim, record);
+			migrated += 1;
+		}
+
+		T::DbWeight::get().reads_writes(entries.len() as Weight, migrated * 2)
+	}
+
+	/// SCALE encoded `ClaimDebug` of a claim, `None` if it does not exist.
+	pub fn claim_debug(claim: &[u8]) -> Option<Vec<u8>> {
+		if !Proofs::<T>::contains_key(claim) {
+			return None;
+		}
+		let (owner, block_number) = Proofs::<T>::get(claim);
+
+		Some(ClaimDebug {
+			owner,
+			block_number,
+			timestamp: Self::claim_timestamp(claim),
+			transfer_unlock: Self::transfer_unlock(claim),
+			activates_at: Self::activates_at(claim),
+			last_transfer_block: Self::last_transfer_block(claim),
+			last_proven_block: Self::last_proven_block(claim),
+			lease: Self::lease(claim),
+			cosigner: Self::cosigner(claim),
+			cosigned: Self::cosigned_transfer(claim),
+			transfer_allowlist: Self::transfer_allowlist(claim),
+			challenge: Self::challenge(claim),
+			transfer_request: Self::transfer_request(claim),
+			renounced: Self::renounced(claim),
+			lock: Self::lock(claim),
+			co_owners: Self::co_owners(claim),
+			pending_actions: Self::pending_actions(claim),
+			scoped_approvals: Self::scoped_approvals(claim),
+			uri: Self::claim_uri(claim),
+		}.encode())
+	}
+
+	/// Owner of a claim and the recipient of its pending transfer request, `None` if the claim
+	/// does not exist.
+	pub fn effective_ownership(claim: &[u8]) -> Option<(T::AccountId, Option<T::AccountId>)> {
+		if !Proofs::<T>::contains_key(claim) {
+			return None;
+		}
+		let (owner, _block_number) = Proofs::<T>::get(claim);
+
+		Some((owner, Self::transfer_request(claim).map(|(recipient, _fee)| recipient)))
+	}
+
+	/// Records of up to `MAX_QUERY_LIMIT` claims, in the order given. Further claims are ignored.
+	pub fn claim_records(claims: Vec<Vec<u8>>) -> Vec<Option<ClaimRecord<T::AccountId, T::BlockNumber>>> {
+		claims.iter()
+			.take(MAX_QUERY_LIMIT as usize)
+			.map(|claim| Self::claim_record(claim))
+			.collect()
+	}
+
+	/// Owners of up to `MAX_QUERY_LIMIT` claims, in the order given. Further claims are ignored.
+	pub fn ownership_of(claims: Vec<Vec<u8>>) -> Vec<Option<T::AccountId>> {
+		claims.iter()
+			.take(MAX_QUERY_LIMIT as usize)
+			.map(|claim| if Proofs::<T>::contains_key(claim) { Some(Proofs::<T>::get(claim).0) } else { None })
+			.collect()
+	}
+
+	/// Content-addressed claim id binding `content` to `creator`.
+	pub fn derive_claim_id(creator: &T::AccountId, content: &[u8]) -> [u8; 32] {
+		(creator, content).using_encoded(blake2_256)
+	}
+
+	/// Storage key of a newly submitted claim: the hex decoded bytes when `HexClaims` is set,
+	/// the claim itself otherwise. An optional `0x` prefix and either letter case are accepted.
+	///
+	/// Applied by the creation calls only; calls on an existing claim expect the stored key.
+	pub fn normalize_claim(claim: Vec<u8>) -> Result<Vec<u8>, Error<T>> {
+		if !T::HexClaims::get() {
+			return Ok(claim);
+		}
+		let digits = if claim.starts_with(b"0x") { &claim[2..] } else { &claim[..] };
+		if digits.len() % 2 != 0 {
+			return Err(Error::<T>::InvalidClaimFormat);
+		}
+		digits.chunks(2)
+			.map(|pair| Some(hex_value(pair[0])? << 4 | hex_value(pair[1])?))
+			.collect::<Option<Vec<u8>>>()
+			.ok_or(Error::<T>::InvalidClaimFormat)
+	}
+
+	/// Length of a claim as checked against `MaxClaimLength`.
+	///
+	/// Counts characters when `CountClaimChars` is set and the claim is valid UTF-8, bytes otherwise.
+	pub fn claim_length(claim: &[u8]) -> usize {
+		if T::CountClaimChars::get() {
+			if let Ok(text) = sp_std::str::from_utf8(claim) {
+				return text.chars().count();
+			}
+		}
+		claim.len()
+	}
+
+	/// Oldest and newest claims by stored block number, scanning at most `MAX_QUERY_SCAN` entries.
+	pub fn claim_bounds() -> (Option<(Vec<u8>, T::BlockNumber)>, Option<(Vec<u8>, T::BlockNumber)>) {
+		let mut oldest: Option<(Vec<u8>, T::BlockNumber)> = None;
+		let mut newest: Option<(Vec<u8>, T::BlockNumber)> = None;
+
+		for (claim, (_owner, block_number)) in Proofs::<T>::iter().take(MAX_QUERY_SCAN as usize) {
+			if oldest.as_ref().map_or(true, |(_, oldest_block)| block_number < *oldest_block) {
+				oldest = Some((claim.clone(), block_number));
+			}
+			if newest.as_ref().map_or(true, |(_, newest_block)| block_number > *newest_block) {
+				newest = Some((claim, block_number));
+			}
+		}
+
+		(oldest, newest)
+	}
+
+	/// Lease of a claim that has not yet ended; expired leases are ignored.
+	pub fn active_lease(claim: &[u8]) -> Option<(T::AccountId, T::BlockNumber)> {
+		Self::lease(claim).filter(|(_, until)| system::Module::<T>::block_number() < *until)
+	}
+
+	/// Account in control of a claim: the lessee during an active lease, the owner otherwise.
+	pub fn claim_controller(claim: &[u8]) -> Option<T::AccountId> {
+		if !Proofs::<T>::contains_key(claim) {
+			return None;
+		}
+		Self::active_lease(claim)
+			.map(|(lessee, _)| lessee)
+			.or_else(|| Some(Proofs::<T>::get(claim).0))
+	}
+
+	/// Activation block of `claim` while it is still pending.
+	pub fn pending_until(claim: &[u8]) -> Option<T::BlockNumber> {
+		Self::activates_at(claim).filter(|at| system::Module::<T>::block_number() < *at)
+	}
+
+	/// Whether `claim` is locked at the current block.
+	pub fn is_locked(claim: &[u8]) -> bool {
+		match Self::lock(claim) {
+			None => false,
+			Some(None) => true,
+			Some(Some(until)) => system::Module::<T>::block_number() < until,
+		}
+	}
+
+	/// Status of an existing claim, `None` if it does not exist.
+	pub fn claim_status(claim: &[u8]) -> Option<ClaimStatus> {
+		if !Proofs::<T>::contains_key(claim) {
+			return None;
+		}
+		if Self::pending_until(claim).is_some() {
+			Some(ClaimStatus::Pending)
+		} else if Self::is_dormant(claim) {
+			Some(ClaimStatus::Dormant)
+		} else {
+			Some(ClaimStatus::Active)
+		}
+	}
+
+	/// Whether control of `claim` was last shown, by `prove_control` or by creation or transfer,
+	/// more than `ProofOfControlInterval` blocks ago.
+	pub fn is_dormant(claim: &[u8]) -> bool {
+		let (_owner, block_number) = Proofs::<T>::get(claim);
+		let proven = Self::last_proven_block(claim).map_or(block_number, |proven| proven.max(block_number));
+
+		system::Module::<T>::block_number() > proven + T::ProofOfControlInterval::get()
+	}
+
+	/// Full record of a claim, `None` if it does not exist.
+	pub fn claim_record(claim: &[u8]) -> Option<ClaimRecord<T::AccountId, T::BlockNumber>> {
+		if !Proofs::<T>::contains_key(claim) {
+			return None;
+		}
+		let (owner, block_number) = Proofs::<T>::get(claim);
+
+		Some(ClaimRecord {
+			owner,
+			block_number,
+			transfer_unlock: Self::transfer_unlock(claim),
+			lease: Self::active_lease(claim),
+			activates_at: Self::pending_until(claim),
+			lock: Self::lock(claim).filter(|_| Self::is_locked(claim)),
+		})
+	}
+
+	/// Whether `uri` starts with one of the accepted schemes.
+	fn has_valid_scheme(uri: &[u8]) -> bool {
+		[&b"http://"[..], b"https://", b"ipfs://"].iter().any(|scheme| uri.starts_with(scheme))
+	}
+
+	/// Deposit reserved for creating `claim`, growing with its length.
+	pub fn claim_deposit(claim: &[u8]) -> BalanceOf<T> {
+		T::PerByteDeposit::get()
+			.saturating_mul(BalanceOf::<T>::from(claim.len() as u32))
+			.saturating_add(T::BaseDeposit::get())
+	}
+
+	/// Reserve the deposit of a new `claim` from `depositor`, who gets it back when the claim
+	/// is removed. Signed creation paths call this as their last fallible step.
+	fn reserve_deposit(depositor: &T::AccountId, claim: &[u8]) -> dispatch::DispatchResult {
+		let deposit = Self::claim_deposit(claim);
+		if !deposit.is_zero() {
+			T::Currency::reserve(depositor, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+			ClaimDeposits::<T>::insert(claim, (depositor.clone(), deposit));
+		}
+
+		Ok(())
+	}
+
+	fn ensure_can_create(claim: &[u8]) -> dispatch::DispatchResult {
+		// 附加题答案
+		ensure!(T::MaxClaimLength::get() >= Self::claim_length(claim) as u32, Error::<T>::ProofTooLong);
+
+		Self::ensure_can_store(claim)
+	}
+
+	fn ensure_can_store(claim: &[u8]) -> dispatch::DispatchResult {
+		ensure!(!Proofs::<T>::contains_key(claim), Error::<T>::ProofAlreadyExist);
+		ensure!(!Self::tombstoned(claim), Error::<T>::ClaimTombstoned);
+
+		let total = Self::total_claim_bytes().saturating_add(claim.len() as u64);
+		ensure!(total <= T::MaxTotalClaimBytes::get(), Error::<T>::StorageBudgetExceeded);
+
+		Ok(())
+	}
+
+	/// Last block at which a commitment made at `committed_at` can be revealed.
+	fn reveal_deadline(committed_at: T::BlockNumber) -> T::BlockNumber {
+		committed_at + T::RevealDelay::get() + T::RevealWindow::get()
+	}
+
+	/// Permissions `delegate` holds over `claim`, none if it was not approved.
+	pub fn delegate_permissions(claim: &[u8], delegate: &T::AccountId) -> DelegatePermissions {
+		Self::scoped_approvals(claim)
+			.into_iter()
+			.find(|(account, _)| account == delegate)
+			.map(|(_, permissions)| permissions)
+			.unwrap_or_default()
+	}
+
+	/// Check every revocation restriction for `sender` revoking `claim`, without touching storage.
+	fn ensure_can_revoke(sender: &T::AccountId, claim: &[u8]) -> dispatch::DispatchResult {
+		ensure!(Proofs::<T>::contains_key(claim), Error::<T>::ClaimNotExist);
+
+		let (owner, _block_number) = Proofs::<T>::get(claim);
+
+		ensure!(owner == *sender, Error::<T>::NotClaimOwner);
+
+		ensure!(Self::active_lease(claim).is_none(), Error::<T>::ClaimLeased);
+		ensure!(Self::pending_until(claim).is_none(), Error::<T>::ClaimPending);
+		ensure!(!Self::renounced(claim), Error::<T>::ClaimRenounced);
+		ensure!(!Self::is_locked(claim), Error::<T>::ClaimLocked);
+
+		Ok(())
+	}
+
+	/// Revoke `claim` owned by `sender`, enforcing every revocation restriction.
+	fn do_revoke(sender: T::AccountId, claim: Vec<u8>) -> dispatch::DispatchResult {
+		Self::ensure_can_revoke(&sender, &claim)?;
+
+		Self::remove_claim(&claim);
+
+		Self::emit_event(RawEvent::ClaimRevoked(sender, claim));
+
+		Ok(())
+	}
+
+	/// Record `sender`'s approval of a pending action and run it once `threshold` co-owners approved.
+	/// A failing action keeps its earlier approvals and can be approved again.
+	fn do_approve(sender: T::AccountId, claim: Vec<u8>, action_id: u32) -> dispatch::DispatchResult {
+		let (owners, threshold) = Self::co_owners(&claim).ok_or(Error::<T>::NotCoOwner)?;
+
+		ensure!(owners.contains(&sender), Error::<T>::NotCoOwner);
+
+		let mut actions = Self::pending_actions(&claim);
+		let index = actions.iter().position(|a| a.id == action_id).ok_or(Error::<T>::ActionNotFound)?;
+
+		ensure!(!actions[index].approvals.contains(&sender), Error::<T>::AlreadyApproved);
+
+		if actions[index].approvals.len() + 1 < threshold as usize {
+			actions[index].approvals.push(sender.clone());
+			PendingActions::<T>::insert(&claim, actions);
+			Self::emit_event(RawEvent::ActionApproved(sender, claim, action_id));
+			return Ok(());
+		}
+
+		let (owner, _block_number) = Proofs::<T>::get(&claim);
+		match actions.swap_remove(index).action {
+			ClaimAction::Transfer(dest) => Self::do_transfer(owner, claim.clone(), dest, false)?,
+			ClaimAction::Revoke => Self::do_revoke(owner, claim.clone())?,
+		}
+
+		Self::emit_event(RawEvent::ActionApproved(sender, claim.clone(), action_id));
+		Self::emit_event(RawEvent::ActionExecuted(claim, action_id));
+
+		Ok(())
+	}
+
+	/// Cancel the transfer requests expiring at `now` and release their fees, returning the weight consumed.
+	fn expire_transfer_requests(now: T::BlockNumber) -> Weight {
+		let expiring = ExpiringRequests::<T>::take(now);
+		// The expiring list itself, then per entry its expiry, request and reserve.
+		let entries = expiring.len() as Weight;
+		let weight = T::DbWeight::get().reads_writes(1 + 3 * entries, 1 + 3 * entries);
+
+		for claim in expiring {
+			if Self::transfer_request_expires(&claim) != Some(now) {
+				continue;
+			}
+			if let Some((recipient, fee)) = TransferRequests::<T>::take(&claim) {
+				T::Currency::unreserve(&recipient, fee);
+				Self::emit_event(RawEvent::TransferRequestExpired(recipient, claim.clone()));
+			}
+			TransferRequestExpires::<T>::remove(&claim);
+		}
+
+		weight
+	}
+
+	/// Move `claim` from its owner `sender` to `dest`, enforcing every transfer restriction.
+	fn do_transfer(sender: T::AccountId, claim: Vec<u8>, dest: T::AccountId, carry_lease: bool) -> dispatch::DispatchResult {
+		Self::ensure_can_transfer(&sender, &claim, &dest)?;
+
+		let fee = T::TransferFee::get();
+		if !fee.is_zero() {
+			let imbalance = T::Currency::withdraw(
+				&sender,
+				fee,
+				WithdrawReasons::from(WithdrawReason::Transfer),
+				ExistenceRequirement::KeepAlive,
+			).map_err(|_| Error::<T>::InsufficientBalance)?;
+			T::TransferFeeDestination::on_unbalanced(imbalance);
+		}
+
+		CosignedTransfer::<T>::remove(&claim);
+		TransferAllowlist::<T>::remove(&claim);
+		LastTransferBlock::<T>::insert(&claim, system::Module::<T>::block_number());
+		if !carry_lease {
+			if Self::active_lease(&claim).is_some() {
+				Self::emit_event(RawEvent::LeaseCancelled(sender.clone(), claim.clone()));
+			}
+			Leases::<T>::remove(&claim);
+		}
+		Self::change_owner(&claim, &sender, &dest);
+
+		let nonce = system::Module::<T>::account_nonce(&dest);
+
+		Self::emit_event(RawEvent::ClaimTransferred(sender, dest, claim, nonce));
+
+		Ok(())
+	}
+
+	/// Check every transfer restriction for moving `claim` from its owner `sender` to `dest`.
+	fn ensure_can_transfer(sender: &T::AccountId, claim: &[u8], dest: &T::AccountId) -> dispatch::DispatchResult {
+		ensure!(Proofs::<T>::contains_key(claim), Error::<T>::ClaimNotExist);
+
+		let (owner, _block_number) = Proofs::<T>::get(claim);
+
+		ensure!(owner == *sender, Error::<T>::NotClaimOwner);
+
+		let now = system::Module::<T>::block_number();
+		if let Some(unlock_at) = TransferUnlock::<T>::get(claim) {
+			ensure!(now >= unlock_at, Error::<T>::TransferTimelocked);
+		}
+
+		ensure!(!Challenges::<T>::contains_key(claim), Error::<T>::ClaimChallenged);
+		ensure!(Self::pending_until(claim).is_none(), Error::<T>::ClaimPending);
+		ensure!(!Self::renounced(claim), Error::<T>::ClaimRenounced);
+		ensure!(!Self::is_locked(claim), Error::<T>::ClaimLocked);
+
+		if RequiresCosign::<T>::contains_key(claim) {
+			let approved = Self::cosigned_transfer(claim).filter(|(to, until)| to == dest && now <= *until);
+			ensure!(approved.is_some(), Error::<T>::CosignRequired);
+		}
+
+		if let Some(last_transfer) = Self::last_transfer_block(claim) {
+			ensure!(now >= last_transfer + T::TransferCooldown::get(), Error::<T>::TransferTooSoon);
+		}
+
+		if T::RequireExistingAccount::get() {
+			ensure!(system::Account::<T>::contains_key(dest), Error::<T>::DestinationNotFound);
+		}
+
+		let allowlist = Self::transfer_allowlist(claim);
+		ensure!(allowlist.is_empty() || allowlist.contains(dest), Error::<T>::DestinationNotAllowed);
+
+		let fee = T::TransferFee::get();
+		if !fee.is_zero() {
+			let new_balance = T::Currency::free_balance(sender)
+				.checked_sub(&fee)
+				.filter(|balance| *balance >= T::Currency::minimum_balance())
+				.ok_or(Error::<T>::InsufficientBalance)?;
+			T::Currency::ensure_can_withdraw(sender, fee, WithdrawReasons::from(WithdrawReason::Transfer), new_balance)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+		}
+
+		Ok(())
+	}
+
+	fn ensure_within_quota(owner: &T::AccountId) -> dispatch::DispatchResult {
+		let quota = Self::creator_quota(owner).unwrap_or_else(T::MaxClaimsPerOwner::get);
+
+		ensure!(Self::owned_claim_count(owner) < quota, Error::<T>::TooManyClaims);
+
+		Ok(())
+	}
+
+	fn insert_claim(owner: &T::AccountId, claim: &[u8]) {
+		Proofs::<T>::insert(claim, (owner.clone(), system::Module::<T>::block_number()));
+		ClaimTimestamp::<T>::insert(claim, pallet_timestamp::Module::<T>::get());
+		let delay = T::ActivationDelay::get();
+		if !delay.is_zero() {
+			ActivatesAt::<T>::insert(claim, system::Module::<T>::block_number() + delay);
+		}
+		if Self::is_counted(claim) {
+			OwnedClaimCount::<T>::mutate(owner, |count| *count = count.saturating_add(1));
+			ClaimsOf::<T>::mutate(owner, |claims| claims.push(claim.to_vec()));
+			Self::note_claim_added(claim);
+		}
+	}
+
+	fn remove_claim(claim: &[u8]) {
+		let (owner, _block_number) = Proofs::<T>::take(claim);
+		let counted = Self::is_counted(claim);
+		if counted {
+			OwnedClaimCount::<T>::mutate(&owner, |count| *count = count.saturating_sub(1));
+			Self::unindex_claim(&owner, claim);
+		}
+		TransferUnlock::<T>::remove(claim);
+		RequiresCosign::<T>::remove(claim);
+		CosignedTransfer::<T>::remove(claim);
+		Leases::<T>::remove(claim);
+		LastTransferBlock::<T>::remove(claim);
+		ClaimTimestamp::<T>::remove(claim);
+		TransferAllowlist::<T>::remove(claim);
+		ActivatesAt::<T>::remove(claim);
+		Renounced::remove(claim);
+		LastProvenBlock::<T>::remove(claim);
+		Locks::<T>::remove(claim);
+		CoOwners::<T>::remove(claim);
+		PendingActions::<T>::remove(claim);
+		ScopedApprovals::<T>::remove(claim);
+		Self::release_deposit(claim);
+		if let Some((challenger, bond)) = Challenges::<T>::take(claim) {
+			T::Currency::unreserve(&challenger, bond);
+		}
+		Self::cancel_transfer_request(claim);
+		if counted {
+			Self::note_claim_removed(claim);
+		}
+		ClaimUri::remove(claim);
+	}
+
+	fn change_owner(claim: &[u8], from: &T::AccountId, to: &T::AccountId) {
+		Proofs::<T>::insert(claim, (to.clone(), system::Module::<T>::block_number()));
+		if Self::is_counted(claim) {
+			OwnedClaimCount::<T>::mutate(from, |count| *count = count.saturating_sub(1));
+			OwnedClaimCount::<T>::mutate(to, |count| *count = count.saturating_add(1));
+			Self::unindex_claim(from, claim);
+			ClaimsOf::<T>::mutate(to, |claims| claims.push(claim.to_vec()));
+		}
+		// Sharing, delegation and cosigning are set up by an owner and do not carry over to the next.
+		CoOwners::<T>::remove(claim);
+		PendingActions::<T>::remove(claim);
+		ScopedApprovals::<T>::remove(claim);
+		RequiresCosign::<T>::remove(claim);
+		CosignedTransfer::<T>::remove(claim);
+		// A request by anyone else is void now; one by `to` is settled by `approve_incoming_transfer`.
+		if Self::transfer_request(claim).map_or(false, |(recipient, _fee)| recipient != *to) {
+			Self::cancel_transfer_request(claim);
+		}
+	}
+
+	/// Return the deposit held for `claim`, if any, to its depositor.
+	fn release_deposit(claim: &[u8]) {
+		if let Some((depositor, deposit)) = ClaimDeposits::<T>::take(claim) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+	}
+
+	/// Drop the transfer request on `claim`, if any, returning the held fee to the requester.
+	fn cancel_transfer_request(claim: &[u8]) {
+		if let Some((recipient, fee)) = TransferRequests::<T>::take(claim) {
+			T::Currency::unreserve(&recipient, fee);
+		}
+		TransferRequestExpires::<T>::remove(claim);
+	}
+
+	/// Bytes `claim` adds to `TotalClaimBytes`: the claim and its URI.
+	fn stored_bytes(claim: &[u8]) -> u64 {
+		(claim.len() + Self::claim_uri(claim).map_or(0, |uri| uri.len())) as u64
+	}
+
+	/// Whether `claim` is reflected in the counters. While `recompute_counters` is running, claims
+	/// past its cursor are not: the run counts them when it gets there.
+	fn is_counted(claim: &[u8]) -> bool {
+		Self::recompute_cursor().map_or(true, |cursor| Proofs::<T>::hashed_key_for(claim) <= cursor)
+	}
+
+	fn unindex_claim(owner: &T::AccountId, claim: &[u8]) {
+		let mut claims = Self::claims_of(owner);
+		if let Some(index) = claims.iter().position(|c| c[..] == *claim) {
+			claims.swap_remove(index);
+		}
+		if claims.is_empty() {
+			ClaimsOf::<T>::remove(owner);
+		} else {
+			ClaimsOf::<T>::insert(owner, claims);
+		}
+	}
+
+	fn note_claim_added(claim: &[u8]) {
+		ClaimLengthHistogram::mutate(Self::length_bucket(claim.len()), |count| *count = count.saturating_add(1));
+		TotalClaimBytes::mutate(|total| *total = total.saturating_add(Self::stored_bytes(claim)));
+	}
+
+	fn note_claim_removed(claim: &[u8]) {
+		TotalClaimBytes::mutate(|total| *total = total.saturating_sub(Self::stored_bytes(claim)));
+		ClaimLengthHistogram::mutate_exists(Self::length_bucket(claim.len()), |count| {
+			*count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c > 0);
+		});
+	}
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+	match digit {
+		b'0'..=b'9' => Some(digit - b'0'),
+		b'a'..=b'f' => Some(digit - b'a' + 10),
+		b'A'..=b'F' => Some(digit - b'A' + 10),
+		_ => None,
 	}
 }
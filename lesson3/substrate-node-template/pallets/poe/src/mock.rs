@@ -1,10 +1,13 @@
 // Creating mock runtime here
 
 use crate::{Module, Trait};
+use std::cell::RefCell;
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{
+	impl_outer_origin, impl_outer_event, parameter_types, traits::{Currency, Get, OnUnbalanced}, weights::{RuntimeDbWeight, Weight},
+};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, UintAuthorityId}, Perbill,
 };
 use frame_system as system;
 
@@ -12,16 +15,29 @@ impl_outer_origin! {
 	pub enum Origin for Test {}
 }
 
+mod poe {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		poe<T>,
+	}
+}
+
 // For testing the pallet, we construct most of a mock runtime. This means
 // first constructing a configuration type (`Test`) which `impl`s each of the
 // configuration traits of pallets we want to use.
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 10 };
 }
 impl system::Trait for Test {
 	type Origin = Origin;
@@ -33,10 +49,10 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
-	type DbWeight = ();
+	type DbWeight = DbWeight;
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
@@ -44,22 +60,200 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type ModuleToIndex = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 }
 
+thread_local! {
+	static EMIT_EVENTS: RefCell<bool> = RefCell::new(true);
+	static COUNT_CLAIM_CHARS: RefCell<bool> = RefCell::new(false);
+	static MAX_TOTAL_CLAIM_BYTES: RefCell<u64> = RefCell::new(1024);
+	static REQUIRE_EXISTING_ACCOUNT: RefCell<bool> = RefCell::new(false);
+	static TRANSFER_FEE: RefCell<u64> = RefCell::new(0);
+	static HEX_CLAIMS: RefCell<bool> = RefCell::new(false);
+	static ACTIVATION_DELAY: RefCell<u64> = RefCell::new(0);
+	static CLAIM_DEPOSIT: RefCell<(u64, u64)> = RefCell::new((0, 0));
+}
+
+pub struct EmitEvents;
+impl Get<bool> for EmitEvents {
+	fn get() -> bool {
+		EMIT_EVENTS.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_emit_events(emit: bool) {
+	EMIT_EVENTS.with(|v| *v.borrow_mut() = emit);
+}
+
+pub struct CountClaimChars;
+impl Get<bool> for CountClaimChars {
+	fn get() -> bool {
+		COUNT_CLAIM_CHARS.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_count_claim_chars(count_chars: bool) {
+	COUNT_CLAIM_CHARS.with(|v| *v.borrow_mut() = count_chars);
+}
+
+pub struct MaxTotalClaimBytes;
+impl Get<u64> for MaxTotalClaimBytes {
+	fn get() -> u64 {
+		MAX_TOTAL_CLAIM_BYTES.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_max_total_claim_bytes(max: u64) {
+	MAX_TOTAL_CLAIM_BYTES.with(|v| *v.borrow_mut() = max);
+}
+
+pub struct RequireExistingAccount;
+impl Get<bool> for RequireExistingAccount {
+	fn get() -> bool {
+		REQUIRE_EXISTING_ACCOUNT.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_require_existing_account(require: bool) {
+	REQUIRE_EXISTING_ACCOUNT.with(|v| *v.borrow_mut() = require);
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+}
+
+pub struct TransferFee;
+impl Get<u64> for TransferFee {
+	fn get() -> u64 {
+		TRANSFER_FEE.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_transfer_fee(fee: u64) {
+	TRANSFER_FEE.with(|v| *v.borrow_mut() = fee);
+}
+
+pub struct HexClaims;
+impl Get<bool> for HexClaims {
+	fn get() -> bool {
+		HEX_CLAIMS.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_hex_claims(hex: bool) {
+	HEX_CLAIMS.with(|v| *v.borrow_mut() = hex);
+}
+
+pub struct ActivationDelay;
+impl Get<u64> for ActivationDelay {
+	fn get() -> u64 {
+		ACTIVATION_DELAY.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_activation_delay(delay: u64) {
+	ACTIVATION_DELAY.with(|v| *v.borrow_mut() = delay);
+}
+
+pub struct BaseDeposit;
+impl Get<u64> for BaseDeposit {
+	fn get() -> u64 {
+		CLAIM_DEPOSIT.with(|v| v.borrow().0)
+	}
+}
+
+pub struct PerByteDeposit;
+impl Get<u64> for PerByteDeposit {
+	fn get() -> u64 {
+		CLAIM_DEPOSIT.with(|v| v.borrow().1)
+	}
+}
+
+pub fn set_claim_deposit(base: u64, per_byte: u64) {
+	CLAIM_DEPOSIT.with(|v| *v.borrow_mut() = (base, per_byte));
+}
+
+/// Account collecting transfer fees in tests.
+pub const FEE_COLLECTOR: u64 = 9;
+
+pub struct FeeCollector;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for FeeCollector {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&FEE_COLLECTOR, amount);
+	}
+}
+
 parameter_types! {
 	pub const MaxClaimLength: u32 = 6;
+	pub const MaxClaimsPerOwner: u32 = 3;
+	pub const MaxBookmarks: u32 = 2;
+	pub const TransferCooldown: u64 = 5;
+	pub const ChallengeBond: u64 = 10;
+	pub const RevealDelay: u64 = 2;
+	pub const RevealWindow: u64 = 5;
+	pub const ProofOfControlInterval: u64 = 10;
+	pub const IncomingTransferFee: u64 = 10;
+	pub const MaxUriLength: u32 = 32;
+	pub const RequestExpiry: u64 = 10;
+	pub const MaxPendingActions: u32 = 2;
 }
 impl Trait for Test {
-	type Event = ();
+	type Event = TestEvent;
 	type MaxClaimLength = MaxClaimLength;
+	type EmitEvents = EmitEvents;
+	type Signature = TestSignature;
+	type Public = UintAuthorityId;
+	type MaxClaimsPerOwner = MaxClaimsPerOwner;
+	type CountClaimChars = CountClaimChars;
+	type MaxTotalClaimBytes = MaxTotalClaimBytes;
+	type MaxBookmarks = MaxBookmarks;
+	type RequireExistingAccount = RequireExistingAccount;
+	type TransferCooldown = TransferCooldown;
+	type Currency = Balances;
+	type ChallengeBond = ChallengeBond;
+	type TransferFee = TransferFee;
+	type TransferFeeDestination = FeeCollector;
+	type HexClaims = HexClaims;
+	type ActivationDelay = ActivationDelay;
+	type RevealDelay = RevealDelay;
+	type RevealWindow = RevealWindow;
+	type ProofOfControlInterval = ProofOfControlInterval;
+	type IncomingTransferFee = IncomingTransferFee;
+	type MaxUriLength = MaxUriLength;
+	type BaseDeposit = BaseDeposit;
+	type PerByteDeposit = PerByteDeposit;
+	type RequestExpiry = RequestExpiry;
+	type MaxPendingActions = MaxPendingActions;
 }
 pub type PoeModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Timestamp = pallet_timestamp::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(3, 100), (4, 5)],
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
 }
@@ -0,0 +1,23 @@
+//! Helpers for setting up claims in tests, for this pallet and pallets composing with it.
+
+use crate::{Module, Proofs, Trait};
+use frame_support::StorageMap;
+use sp_std::prelude::*;
+
+/// Store `claim` for `owner` at the current block without going through a dispatchable.
+pub fn seed_claim<T: Trait>(owner: &T::AccountId, claim: &[u8]) {
+	Module::<T>::insert_claim(owner, claim);
+}
+
+/// Store every `(owner, claim)` pair, see `seed_claim`.
+pub fn seed_claims<T: Trait>(claims: Vec<(T::AccountId, Vec<u8>)>) {
+	for (owner, claim) in claims {
+		seed_claim::<T>(&owner, &claim);
+	}
+}
+
+/// Panic unless `claim` exists and is owned by `owner`.
+pub fn assert_claim_owner<T: Trait>(claim: &[u8], owner: &T::AccountId) {
+	assert!(Proofs::<T>::contains_key(claim), "claim {:?} does not exist", claim);
+	assert_eq!(&Proofs::<T>::get(claim).0, owner);
+}
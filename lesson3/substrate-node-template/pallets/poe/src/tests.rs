@@ -1,5 +1,2403 @@
 // Tests to be written here
 
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{
+	ClaimAction, ClaimDebug, ClaimLengthHistogram, ClaimRecord, ClaimStatus, DelegatePermissions, Error, OwnedClaimCount, Proofs,
+	RawEvent, TotalClaimBytes, MAX_QUERY_LIMIT, REMOVE_CLAIM_READS, REMOVE_CLAIM_WRITES, REVOKE_CLAIM_WEIGHT, mock::*, testing,
+};
+use frame_support::{assert_ok, assert_noop, traits::OnInitialize, StorageMap, StorageValue};
+use sp_runtime::{DispatchError, testing::TestSignature};
 
+#[test]
+fn transfer_claim_fails_before_unlock_block() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_transfer_unlock(Origin::signed(1), claim.clone(), 10));
+
+		System::set_block_number(9);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::TransferTimelocked
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_works_at_unlock_block() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_transfer_unlock(Origin::signed(1), claim.clone(), 10));
+
+		System::set_block_number(10);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::proofs(&claim), (2, 10));
+	});
+}
+
+#[test]
+fn set_transfer_unlock_fails_for_non_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::set_transfer_unlock(Origin::signed(2), claim.clone(), 10),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn force_transfer_claim_works_for_timelocked_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_transfer_unlock(Origin::signed(1), claim.clone(), 10));
+
+		assert_ok!(PoeModule::force_transfer_claim(Origin::ROOT, claim.clone(), 2));
+		assert_eq!(PoeModule::proofs(&claim), (2, 0));
+		assert_eq!(PoeModule::transfer_unlock(&claim), None);
+		assert_eq!(PoeModule::last_transfer_block(&claim), Some(0));
+	});
+}
+
+#[test]
+fn force_transfer_claim_ends_lease() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 3, 9));
+
+		assert_ok!(PoeModule::force_transfer_claim(Origin::ROOT, claim.clone(), 2));
+		assert_eq!(PoeModule::lease(&claim), None);
+		assert_eq!(PoeModule::active_lease(&claim), None);
+	});
+}
+
+#[test]
+fn force_transfer_claim_unlocks_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lock_claim(Origin::signed(1), claim.clone(), None));
+
+		assert_ok!(PoeModule::force_transfer_claim(Origin::ROOT, claim.clone(), 2));
+		assert!(!PoeModule::is_locked(&claim));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone(), false));
+	});
+}
+
+#[test]
+fn force_transfer_claim_dismisses_challenge() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::challenge_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(Balances::reserved_balance(3), 10);
+
+		assert_ok!(PoeModule::force_transfer_claim(Origin::ROOT, claim.clone(), 2));
+		assert_eq!(PoeModule::challenge(&claim), None);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+	});
+}
+
+#[test]
+fn force_transfer_claim_restores_renounced_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::renounce_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(PoeModule::force_transfer_claim(Origin::ROOT, claim.clone(), 2));
+		assert!(!PoeModule::renounced(&claim));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone(), false));
+	});
+}
+
+#[test]
+fn force_transfer_claim_fails_for_non_root() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::force_transfer_claim(Origin::signed(1), claim.clone(), 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn force_transfer_claim_fails_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::force_transfer_claim(Origin::ROOT, vec![0, 1], 2),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn length_bucket_works() {
+	assert_eq!(PoeModule::length_bucket(0), 0);
+	assert_eq!(PoeModule::length_bucket(1), 1);
+	assert_eq!(PoeModule::length_bucket(2), 2);
+	assert_eq!(PoeModule::length_bucket(3), 2);
+	assert_eq!(PoeModule::length_bucket(4), 3);
+	assert_eq!(PoeModule::length_bucket(6), 3);
+}
+
+#[test]
+fn claim_length_histogram_tracks_create_and_revoke() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0, 1, 2]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![0, 1, 2, 3, 4]));
+
+		assert_eq!(PoeModule::claim_length_buckets(), vec![(1, 1), (2, 2), (3, 1)]);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![0], false));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![0, 1], false));
+
+		assert_eq!(PoeModule::claim_length_histogram(1), 0);
+		assert_eq!(PoeModule::claim_length_buckets(), vec![(2, 1), (3, 1)]);
+	});
+}
+
+#[test]
+fn replace_claim_works() {
+	new_test_ext().execute_with(|| {
+		let old_claim = vec![0, 1];
+		let new_claim = vec![2, 3];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), old_claim.clone()));
+
+		assert_ok!(PoeModule::replace_claim(Origin::signed(1), old_claim.clone(), new_claim.clone()));
+		assert!(!Proofs::<Test>::contains_key(&old_claim));
+		assert_eq!(PoeModule::proofs(&new_claim), (1, 0));
+	});
+}
+
+#[test]
+fn replace_claim_keeps_old_claim_when_new_claim_exists() {
+	new_test_ext().execute_with(|| {
+		let old_claim = vec![0, 1];
+		let new_claim = vec![2, 3];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), old_claim.clone()));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), new_claim.clone()));
+
+		assert_noop!(
+			PoeModule::replace_claim(Origin::signed(1), old_claim.clone(), new_claim.clone()),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(PoeModule::proofs(&old_claim), (1, 0));
+		assert_eq!(PoeModule::proofs(&new_claim), (2, 0));
+	});
+}
+
+#[test]
+fn replace_claim_fails_for_non_owner() {
+	new_test_ext().execute_with(|| {
+		let old_claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), old_claim.clone()));
+
+		assert_noop!(
+			PoeModule::replace_claim(Origin::signed(2), old_claim.clone(), vec![2, 3]),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn create_claim_deposits_event() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::ClaimCreated(1, claim)))
+		);
+	});
+}
+
+#[test]
+fn events_are_suppressed_when_disabled() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		set_emit_events(false);
+		let claim = vec![0, 1];
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::proofs(&claim), (2, 1));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone(), false));
+		assert!(!Proofs::<Test>::contains_key(&claim));
+
+		assert!(System::events().is_empty());
+		set_emit_events(true);
+	});
+}
+
+#[test]
+fn seed_claims_stores_claims() {
+	new_test_ext().execute_with(|| {
+		testing::seed_claims::<Test>(vec![(1, vec![0, 1]), (2, vec![2, 3])]);
+
+		testing::assert_claim_owner::<Test>(&[0, 1], &1);
+		testing::assert_claim_owner::<Test>(&[2, 3], &2);
+		assert_eq!(PoeModule::claim_length_buckets(), vec![(2, 2)]);
+	});
+}
+
+#[test]
+#[should_panic]
+fn assert_claim_owner_panics_for_other_owner() {
+	new_test_ext().execute_with(|| {
+		testing::seed_claim::<Test>(&1, &[0, 1]);
+
+		testing::assert_claim_owner::<Test>(&[0, 1], &2);
+	});
+}
+
+#[test]
+fn create_claim_signed_stores_signer_as_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let signature = TestSignature(2, PoeModule::create_claim_payload(&claim, &2));
+
+		assert_ok!(PoeModule::create_claim_signed(Origin::signed(1), claim.clone(), 2, signature));
+		assert_eq!(PoeModule::proofs(&claim), (2, 0));
+		assert_eq!(PoeModule::signature_nonce(2), 1);
+	});
+}
+
+#[test]
+fn create_claim_signed_rejects_forged_signature() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let signature = TestSignature(1, PoeModule::create_claim_payload(&claim, &2));
+
+		assert_noop!(
+			PoeModule::create_claim_signed(Origin::signed(1), claim.clone(), 2, signature),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			PoeModule::create_claim_signed(Origin::signed(1), claim.clone(), 2, TestSignature(2, claim.clone())),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn create_claim_uses_default_quota() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2]));
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), vec![3]),
+			Error::<Test>::TooManyClaims
+		);
+		assert_eq!(PoeModule::owned_claim_count(1), 3);
+	});
+}
+
+#[test]
+fn create_claim_uses_raised_quota() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::set_quota(Origin::ROOT, 2, Some(4)));
+
+		for i in 0..4 {
+			assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![i]));
+		}
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), vec![4]),
+			Error::<Test>::TooManyClaims
+		);
+	});
+}
+
+#[test]
+fn set_quota_fails_for_non_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::set_quota(Origin::signed(1), 1, Some(4)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn claims_with_prefix_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1, 2, 3]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1, 2, 4]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![1, 3]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![2, 1, 2]));
+
+		let mut claims = PoeModule::claims_with_prefix(vec![1, 2], 10);
+		claims.sort();
+		assert_eq!(claims, vec![vec![1, 2, 3], vec![1, 2, 4]]);
+
+		assert_eq!(PoeModule::claims_with_prefix(vec![1], 10).len(), 3);
+		assert_eq!(PoeModule::claims_with_prefix(vec![1], 2).len(), 2);
+		assert!(PoeModule::claims_with_prefix(vec![3], 10).is_empty());
+	});
+}
+
+#[test]
+fn create_claim_counts_multibyte_claim_in_bytes() {
+	new_test_ext().execute_with(|| {
+		// four characters, eight bytes
+		let claim = "éééé".as_bytes().to_vec();
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), claim),
+			Error::<Test>::ProofTooLong
+		);
+	});
+}
+
+#[test]
+fn create_claim_counts_multibyte_claim_in_chars() {
+	new_test_ext().execute_with(|| {
+		set_count_claim_chars(true);
+		let claim = "éééé".as_bytes().to_vec();
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::claim_length(&claim), 4);
+
+		// invalid UTF-8 still counts bytes
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), vec![0xff; 7]),
+			Error::<Test>::ProofTooLong
+		);
+		set_count_claim_chars(false);
+	});
+}
+
+#[test]
+fn transfer_claim_requires_cosign() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::require_cosign(Origin::signed(1), claim.clone(), 3));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::CosignRequired
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_works_after_cosign() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::require_cosign(Origin::signed(1), claim.clone(), 3));
+
+		assert_noop!(
+			PoeModule::cosign_transfer(Origin::signed(2), claim.clone(), 2),
+			Error::<Test>::NotCosigner
+		);
+		assert_ok!(PoeModule::cosign_transfer(Origin::signed(3), claim.clone(), 2));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::proofs(&claim), (2, 0));
+		assert_eq!(PoeModule::cosigner(&claim), None);
+		assert_eq!(PoeModule::cosigned_transfer(&claim), None);
+	});
+}
+
+#[test]
+fn cosign_only_approves_its_destination_until_it_expires() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::require_cosign(Origin::signed(1), claim.clone(), 3));
+		assert_ok!(PoeModule::cosign_transfer(Origin::signed(3), claim.clone(), 2));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 5, false),
+			Error::<Test>::CosignRequired
+		);
+
+		System::set_block_number(12);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::CosignRequired
+		);
+
+		assert_ok!(PoeModule::cosign_transfer(Origin::signed(3), claim.clone(), 2));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::proofs(&claim), (2, 12));
+	});
+}
+
+#[test]
+fn cosigner_does_not_carry_over_to_new_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		// the seller names themselves cosigner, so the buyer could never transfer alone
+		assert_ok!(PoeModule::require_cosign(Origin::signed(1), claim.clone(), 1));
+		assert_ok!(PoeModule::cosign_transfer(Origin::signed(1), claim.clone(), 2));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+
+		System::set_block_number(10);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3, false));
+		assert_eq!(PoeModule::proofs(&claim), (3, 10));
+	});
+}
+
+#[test]
+fn revoke_claim_refunds_weight() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		let info = PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false).unwrap();
+		assert!(info.actual_weight.unwrap() < REVOKE_CLAIM_WEIGHT);
+	});
+}
+
+#[test]
+fn create_claim_respects_storage_budget() {
+	new_test_ext().execute_with(|| {
+		set_max_total_claim_bytes(20);
+		for who in 1..4 {
+			assert_ok!(PoeModule::create_claim(Origin::signed(who), vec![who as u8; 6]));
+		}
+		assert_ok!(PoeModule::create_claim(Origin::signed(4), vec![0, 1]));
+		assert_eq!(PoeModule::total_claim_bytes(), 20);
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(4), vec![2]),
+			Error::<Test>::StorageBudgetExceeded
+		);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(4), vec![0, 1], false));
+		assert_eq!(PoeModule::total_claim_bytes(), 18);
+		assert_ok!(PoeModule::create_claim(Origin::signed(4), vec![2]));
+		set_max_total_claim_bytes(1024);
+	});
+}
+
+#[test]
+fn claim_bounds_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PoeModule::claim_bounds(), (None, None));
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![3]));
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		System::set_block_number(5);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![5]));
+
+		assert_eq!(PoeModule::claim_bounds(), (Some((vec![1], 1)), Some((vec![5], 5))));
+	});
+}
+
+#[test]
+fn lease_claim_grants_control_until_lease_end() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 2, 10));
+
+		assert_eq!(PoeModule::active_lease(&claim), Some((2, 10)));
+		assert_eq!(PoeModule::claim_controller(&claim), Some(2));
+
+		System::set_block_number(10);
+		assert_eq!(PoeModule::active_lease(&claim), None);
+		assert_eq!(PoeModule::claim_controller(&claim), Some(1));
+	});
+}
+
+#[test]
+fn lessee_can_renew_but_not_transfer_or_revoke() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 2, 10));
+
+		assert_ok!(PoeModule::renew_lease(Origin::signed(2), claim.clone(), 20));
+		assert_eq!(PoeModule::active_lease(&claim), Some((2, 20)));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3, false),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(2), claim.clone(), false),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::renew_lease(Origin::signed(3), claim.clone(), 30),
+			Error::<Test>::NotLessee
+		);
+	});
+}
+
+#[test]
+fn renew_lease_fails_after_lease_end() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 2, 10));
+
+		System::set_block_number(10);
+		assert_noop!(
+			PoeModule::renew_lease(Origin::signed(2), claim.clone(), 20),
+			Error::<Test>::NotLessee
+		);
+	});
+}
+
+#[test]
+fn claim_record_matches_storage() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_eq!(PoeModule::claim_record(&claim), None);
+
+		System::set_block_number(2);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::claim_record(&claim), Some(ClaimRecord {
+			owner: 1,
+			block_number: 2,
+			transfer_unlock: None,
+			lease: None,
+			activates_at: None,
+			lock: None,
+		}));
+
+		assert_ok!(PoeModule::set_transfer_unlock(Origin::signed(1), claim.clone(), 5));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 3, 8));
+		assert_eq!(PoeModule::claim_record(&claim), Some(ClaimRecord {
+			owner: 1,
+			block_number: 2,
+			transfer_unlock: Some(5),
+			lease: Some((3, 8)),
+			activates_at: None,
+			lock: None,
+		}));
+
+		System::set_block_number(8);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 4, false));
+		assert_eq!(PoeModule::claim_record(&claim), Some(ClaimRecord {
+			owner: 4,
+			block_number: 8,
+			transfer_unlock: Some(5),
+			lease: None,
+			activates_at: None,
+			lock: None,
+		}));
+	});
+}
+
+#[test]
+fn revoke_claim_fails_during_lease() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 2, 10));
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false),
+			Error::<Test>::ClaimLeased
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_works_after_lease_cancelled() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 2, 10));
+
+		assert_noop!(
+			PoeModule::cancel_lease(Origin::signed(2), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::cancel_lease(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			PoeModule::cancel_lease(Origin::signed(1), claim.clone()),
+			Error::<Test>::ClaimNotLeased
+		);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false));
+		assert!(!Proofs::<Test>::contains_key(&claim));
+	});
+}
+
+#[test]
+fn bookmark_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(PoeModule::bookmark_claim(Origin::signed(2), claim.clone()));
+		assert_eq!(PoeModule::bookmarks(2), vec![claim.clone()]);
+		assert_eq!(PoeModule::proofs(&claim), (1, 0));
+
+		assert_noop!(
+			PoeModule::bookmark_claim(Origin::signed(2), claim.clone()),
+			Error::<Test>::AlreadyBookmarked
+		);
+		assert_noop!(
+			PoeModule::bookmark_claim(Origin::signed(2), vec![9]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn unbookmark_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::bookmark_claim(Origin::signed(2), claim.clone()));
+
+		assert_ok!(PoeModule::unbookmark_claim(Origin::signed(2), claim.clone()));
+		assert!(PoeModule::bookmarks(2).is_empty());
+
+		assert_noop!(
+			PoeModule::unbookmark_claim(Origin::signed(2), claim.clone()),
+			Error::<Test>::NotBookmarked
+		);
+	});
+}
+
+#[test]
+fn bookmark_claim_respects_max_bookmarks() {
+	new_test_ext().execute_with(|| {
+		for i in 0..3 {
+			assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![i]));
+		}
+		assert_ok!(PoeModule::bookmark_claim(Origin::signed(2), vec![0]));
+		assert_ok!(PoeModule::bookmark_claim(Origin::signed(2), vec![1]));
+
+		assert_noop!(
+			PoeModule::bookmark_claim(Origin::signed(2), vec![2]),
+			Error::<Test>::TooManyBookmarks
+		);
+	});
+}
+
+#[test]
+fn derive_claim_id_binds_content_to_creator() {
+	new_test_ext().execute_with(|| {
+		let content = b"content".to_vec();
+
+		assert_eq!(PoeModule::derive_claim_id(&1, &content), PoeModule::derive_claim_id(&1, &content));
+		assert_ne!(PoeModule::derive_claim_id(&1, &content), PoeModule::derive_claim_id(&2, &content));
+		assert_ne!(PoeModule::derive_claim_id(&1, &content), PoeModule::derive_claim_id(&1, b"other"));
+	});
+}
+
+#[test]
+fn create_derived_claim_works() {
+	new_test_ext().execute_with(|| {
+		let content = b"content".to_vec();
+		let claim = PoeModule::derive_claim_id(&1, &content).to_vec();
+
+		assert_ok!(PoeModule::create_derived_claim(Origin::signed(1), content.clone()));
+		assert_eq!(PoeModule::proofs(&claim), (1, 0));
+
+		assert_noop!(
+			PoeModule::create_derived_claim(Origin::signed(1), content.clone()),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_ok!(PoeModule::create_derived_claim(Origin::signed(2), content));
+	});
+}
+
+#[test]
+fn transfer_claim_to_unknown_account_works_by_default() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::proofs(&claim), (2, 0));
+	});
+}
+
+#[test]
+fn transfer_claim_requires_existing_account_when_enabled() {
+	new_test_ext().execute_with(|| {
+		set_require_existing_account(true);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::DestinationNotFound
+		);
+
+		System::inc_account_nonce(&2);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		set_require_existing_account(false);
+	});
+}
+
+#[test]
+fn transfer_claim_fails_within_cooldown() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		System::set_block_number(1);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+
+		System::set_block_number(5);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3, false),
+			Error::<Test>::TransferTooSoon
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_works_after_cooldown() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		System::set_block_number(1);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+
+		System::set_block_number(6);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3, false));
+		assert_eq!(PoeModule::last_transfer_block(&claim), Some(6));
+	});
+}
+
+#[test]
+fn error_names_match_error_variants() {
+	let errors = PoeModule::error_names();
+
+	assert_eq!(errors[..4].to_vec(), vec![
+		(0, b"ProofAlreadyExist".to_vec()),
+		(1, b"ClaimNotExist".to_vec()),
+		(2, b"NotClaimOwner".to_vec()),
+		(3, b"ProofTooLong".to_vec()),
+	]);
+	assert!(errors.contains(&(Error::<Test>::TransferTooSoon.as_u8(), b"TransferTooSoon".to_vec())));
+	assert!(errors.iter().enumerate().all(|(i, (index, _))| *index as usize == i));
+}
+
+#[test]
+fn create_claim_for_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim_for(Origin::ROOT, claim.clone(), 2));
+
+		assert_eq!(Proofs::<Test>::get(&claim), (2, 0));
+		assert_eq!(PoeModule::owned_claim_count(2), 1);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim, false));
+	})
+}
+
+#[test]
+fn create_claim_for_enforces_claim_checks() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::ROOT, claim, 2),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::ROOT, vec![0; 7], 2),
+			Error::<Test>::ProofTooLong
+		);
+	})
+}
+
+#[test]
+fn create_claim_for_fails_for_non_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::signed(1), vec![0, 1], 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_by_hash_works() {
+	new_test_ext().execute_with(|| {
+		let content = vec![0; 40];
+		assert_ok!(PoeModule::create_derived_claim(Origin::signed(1), content.clone()));
+		let hash = PoeModule::derive_claim_id(&1, &content);
+
+		assert_ok!(PoeModule::revoke_claim_by_hash(Origin::signed(1), hash));
+		assert!(!Proofs::<Test>::contains_key(&hash.to_vec()));
+	})
+}
+
+#[test]
+fn revoke_claim_by_hash_fails_for_unknown_hash() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::revoke_claim_by_hash(Origin::signed(1), [0; 32]),
+			Error::<Test>::ClaimNotExist
+		);
+	})
+}
+
+#[test]
+fn transfer_claim_deposits_event_with_previous_owner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::ClaimTransferred(1, 2, claim, 0)))
+		);
+	});
+}
+
+#[test]
+fn create_claim_records_timestamp() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(42);
+		let claim = vec![0, 1];
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::claim_timestamp(&claim), Some(42));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false));
+		assert_eq!(PoeModule::claim_timestamp(&claim), None);
+	})
+}
+
+#[test]
+fn challenge_claim_reserves_bond_and_blocks_transfer() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(PoeModule::challenge_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(PoeModule::challenge(&claim), Some((3, 10)));
+		assert_eq!(Balances::reserved_balance(3), 10);
+
+		assert_noop!(
+			PoeModule::challenge_claim(Origin::signed(3), claim.clone()),
+			Error::<Test>::AlreadyChallenged
+		);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::ClaimChallenged
+		);
+	})
+}
+
+#[test]
+fn challenge_claim_fails_without_bond() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert!(PoeModule::challenge_claim(Origin::signed(4), claim.clone()).is_err());
+		assert_eq!(PoeModule::challenge(&claim), None);
+	})
+}
+
+#[test]
+fn resolve_challenge_uphold_revokes_claim_and_returns_bond() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::challenge_claim(Origin::signed(3), claim.clone()));
+
+		assert_ok!(PoeModule::resolve_challenge(Origin::ROOT, claim.clone(), true));
+
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(PoeModule::challenge(&claim), None);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+	})
+}
+
+#[test]
+fn resolve_challenge_dismiss_slashes_bond() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::challenge_claim(Origin::signed(3), claim.clone()));
+
+		assert_ok!(PoeModule::resolve_challenge(Origin::ROOT, claim.clone(), false));
+
+		assert_eq!(Proofs::<Test>::get(&claim), (1, 0));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 90);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim, 2, false));
+	})
+}
+
+#[test]
+fn resolve_challenge_fails_for_non_root_or_unchallenged_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::resolve_challenge(Origin::ROOT, claim.clone(), true),
+			Error::<Test>::NotChallenged
+		);
+		assert_ok!(PoeModule::challenge_claim(Origin::signed(3), claim.clone()));
+		assert_noop!(
+			PoeModule::resolve_challenge(Origin::signed(1), claim, true),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn transfer_claim_respects_allowlist() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_transfer_allowlist(Origin::signed(1), claim.clone(), vec![3]));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::DestinationNotAllowed
+		);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 3, false));
+		assert_eq!(Proofs::<Test>::get(&claim), (3, 0));
+		assert!(PoeModule::transfer_allowlist(&claim).is_empty());
+	})
+}
+
+#[test]
+fn empty_allowlist_lifts_restriction() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_transfer_allowlist(Origin::signed(1), claim.clone(), vec![3]));
+		assert_ok!(PoeModule::set_transfer_allowlist(Origin::signed(1), claim.clone(), vec![]));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+	})
+}
+
+#[test]
+fn set_transfer_allowlist_fails_for_non_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::set_transfer_allowlist(Origin::signed(2), claim, vec![2]),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn ensure_claim_creates_missing_claim_once() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+
+		assert_ok!(PoeModule::ensure_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), (1, 1));
+		assert_eq!(System::events().len(), 1);
+
+		assert_ok!(PoeModule::ensure_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::owned_claim_count(1), 1);
+		assert_eq!(System::events().len(), 1);
+	})
+}
+
+#[test]
+fn ensure_claim_fails_when_owned_by_another() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::ensure_claim(Origin::signed(2), claim),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn recompute_counters_repairs_drift() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![0, 1, 2, 3, 4]));
+		OwnedClaimCount::<Test>::insert(1, 7);
+		OwnedClaimCount::<Test>::insert(3, 2);
+		ClaimLengthHistogram::insert(99, 4);
+		TotalClaimBytes::put(1);
+
+		assert_ok!(PoeModule::recompute_counters(Origin::ROOT));
+
+		assert_eq!(PoeModule::owned_claim_count(1), 1);
+		assert_eq!(PoeModule::owned_claim_count(2), 1);
+		assert_eq!(PoeModule::owned_claim_count(3), 0);
+		assert_eq!(PoeModule::claim_length_histogram(99), 0);
+		assert_eq!(PoeModule::total_claim_bytes(), 7);
+	})
+}
+
+#[test]
+fn recompute_counters_fails_for_non_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::recompute_counters(Origin::signed(1)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_charges_fee() {
+	new_test_ext().execute_with(|| {
+		set_transfer_fee(4);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), claim.clone()));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(3), claim.clone(), 2, false));
+		assert_eq!(Proofs::<Test>::get(&claim), (2, 0));
+		assert_eq!(Balances::free_balance(3), 96);
+		assert_eq!(Balances::free_balance(FEE_COLLECTOR), 4);
+		set_transfer_fee(0);
+	})
+}
+
+#[test]
+fn transfer_claim_fails_when_fee_can_not_be_paid() {
+	new_test_ext().execute_with(|| {
+		set_transfer_fee(5);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(4), claim.clone()));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(4), claim.clone(), 2, false),
+			Error::<Test>::InsufficientBalance
+		);
+		set_transfer_fee(0);
+	})
+}
+
+#[test]
+fn hex_claims_are_stored_decoded() {
+	new_test_ext().execute_with(|| {
+		set_hex_claims(true);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), b"0x0a0b".to_vec()));
+		assert_eq!(Proofs::<Test>::get(&vec![10, 11]), (1, 0));
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), b"0A0B".to_vec()),
+			Error::<Test>::ProofAlreadyExist
+		);
+		set_hex_claims(false);
+	})
+}
+
+#[test]
+fn hex_claims_reject_invalid_hex() {
+	new_test_ext().execute_with(|| {
+		set_hex_claims(true);
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), b"0a0".to_vec()),
+			Error::<Test>::InvalidClaimFormat
+		);
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), b"0x0g".to_vec()),
+			Error::<Test>::InvalidClaimFormat
+		);
+		set_hex_claims(false);
+	})
+}
+
+#[test]
+fn all_claims_pages_through_every_claim() {
+	new_test_ext().execute_with(|| {
+		let mut expected = vec![];
+		for (owner, claim) in vec![(1, vec![1]), (1, vec![2]), (1, vec![3]), (2, vec![4]), (2, vec![5])] {
+			assert_ok!(PoeModule::create_claim(Origin::signed(owner), claim.clone()));
+			expected.push((claim, owner, 0));
+		}
+
+		let mut seen = vec![];
+		let mut cursor = None;
+		let mut pages = 0;
+		loop {
+			let (page, next) = PoeModule::all_claims(cursor, 2);
+			assert!(page.len() <= 2);
+			seen.extend(page);
+			pages += 1;
+			if next.is_none() {
+				break;
+			}
+			cursor = next;
+		}
+
+		seen.sort();
+		assert_eq!(seen, expected);
+		assert_eq!(pages, 3);
+	})
+}
+
+#[test]
+fn all_claims_returns_no_cursor_for_last_page() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PoeModule::all_claims(None, 10), (vec![], None));
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2]));
+		let (page, next) = PoeModule::all_claims(None, 2);
+		assert_eq!(page.len(), 2);
+		assert_eq!(next, None);
+	})
+}
+
+#[test]
+fn transfer_claim_with_consent_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		let consent = TestSignature(2, PoeModule::consent_payload(&claim, &1, &2));
+		assert_ok!(PoeModule::transfer_claim_with_consent(Origin::signed(1), claim.clone(), 2, consent));
+		assert_eq!(Proofs::<Test>::get(&claim), (2, 0));
+		assert_eq!(PoeModule::signature_nonce(2), 1);
+	})
+}
+
+#[test]
+fn transfer_claim_with_consent_rejects_invalid_consent() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::transfer_claim_with_consent(
+				Origin::signed(1),
+				claim.clone(),
+				2,
+				TestSignature(3, PoeModule::consent_payload(&claim, &1, &2)),
+			),
+			Error::<Test>::InvalidConsent
+		);
+		assert_noop!(
+			PoeModule::transfer_claim_with_consent(Origin::signed(1), claim.clone(), 2, TestSignature(2, claim.clone())),
+			Error::<Test>::InvalidConsent
+		);
+		assert_noop!(
+			PoeModule::transfer_claim_with_consent(
+				Origin::signed(1),
+				claim.clone(),
+				2,
+				TestSignature(2, PoeModule::create_claim_payload(&claim, &2)),
+			),
+			Error::<Test>::InvalidConsent
+		);
+	})
+}
+
+#[test]
+fn owner_stats_summarizes_holdings() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		System::set_block_number(5);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![3]));
+		System::set_block_number(9);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![4]));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), vec![2], 2, false));
+
+		assert_eq!(PoeModule::owner_stats(&1), (2, Some(3), Some(9)));
+		assert_eq!(PoeModule::owner_stats(&2), (2, Some(5), Some(9)));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1], false));
+		assert_eq!(PoeModule::owner_stats(&1), (1, Some(9), Some(9)));
+	})
+}
+
+#[test]
+fn owner_stats_is_empty_without_claims() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PoeModule::owner_stats(&1), (0, None, None));
+	})
+}
+
+#[test]
+fn transfer_claim_to_multisig_derives_destination() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		// first 8 bytes of blake2_256(b"modlpy/utilisuba" ++ [2, 3].encode() ++ 2u16.encode())
+		let multisig = 1699115465646014126;
+		assert_eq!(PoeModule::multisig_account_id(&[3, 2], 2), multisig);
+		assert_eq!(PoeModule::multisig_account_id(&[2, 3, 3], 2), multisig);
+
+		assert_ok!(PoeModule::transfer_claim_to_multisig(Origin::signed(1), claim.clone(), vec![3, 2], 2));
+		assert_eq!(Proofs::<Test>::get(&claim).0, multisig);
+	})
+}
+
+#[test]
+fn transfer_claim_to_multisig_checks_threshold() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::transfer_claim_to_multisig(Origin::signed(1), claim.clone(), vec![3, 2], 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::transfer_claim_to_multisig(Origin::signed(1), claim.clone(), vec![3, 3], 2),
+			Error::<Test>::InvalidThreshold
+		);
+	});
+}
+
+#[test]
+fn claims_are_pending_until_activation_delay_passes() {
+	new_test_ext().execute_with(|| {
+		set_activation_delay(3);
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_eq!(PoeModule::claim_status(&claim), Some(ClaimStatus::Pending));
+		assert_eq!(PoeModule::claim_record(&claim).unwrap().activates_at, Some(4));
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::ClaimPending
+		);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false),
+			Error::<Test>::ClaimPending
+		);
+
+		System::set_block_number(4);
+		assert_eq!(PoeModule::claim_status(&claim), Some(ClaimStatus::Active));
+		assert_eq!(PoeModule::claim_record(&claim).unwrap().activates_at, None);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		set_activation_delay(0);
+	})
+}
+
+#[test]
+fn claim_status_is_none_for_missing_claim() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PoeModule::claim_status(&[0, 1]), None);
+	})
+}
+
+#[test]
+fn renounced_claim_can_not_be_transferred_or_revoked() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_ok!(PoeModule::renounce_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), (0, 0));
+		assert_eq!(PoeModule::owned_claim_count(1), 0);
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(0), claim.clone(), 2, false),
+			Error::<Test>::ClaimRenounced
+		);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(0), claim.clone(), false),
+			Error::<Test>::ClaimRenounced
+		);
+	})
+}
+
+#[test]
+fn renounce_claim_fails_for_non_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::renounce_claim(Origin::signed(2), claim),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+fn commitment(claim: &[u8], salt: &[u8]) -> [u8; 32] {
+	sp_io::hashing::blake2_256(&[claim, salt].concat())
+}
+
+#[test]
+fn commit_reveal_creates_claim() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		let hash = commitment(&claim, b"salt");
+		assert_ok!(PoeModule::commit_claim(Origin::signed(1), hash));
+
+		assert_noop!(
+			PoeModule::reveal_claim(Origin::signed(1), claim.clone(), b"salt".to_vec()),
+			Error::<Test>::RevealTooEarly
+		);
+		assert_noop!(
+			PoeModule::reveal_claim(Origin::signed(1), claim.clone(), b"pepper".to_vec()),
+			Error::<Test>::CommitmentNotFound
+		);
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::reveal_claim(Origin::signed(1), claim.clone(), b"salt".to_vec()));
+		assert_eq!(Proofs::<Test>::get(&claim), (1, 3));
+		assert_eq!(PoeModule::commitment(&hash), None);
+	})
+}
+
+#[test]
+fn commitment_can_not_be_front_run() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		let hash = commitment(&claim, b"salt");
+		assert_ok!(PoeModule::commit_claim(Origin::signed(1), hash));
+
+		System::set_block_number(3);
+		assert_noop!(
+			PoeModule::commit_claim(Origin::signed(2), hash),
+			Error::<Test>::AlreadyCommitted
+		);
+		assert_noop!(
+			PoeModule::reveal_claim(Origin::signed(2), claim.clone(), b"salt".to_vec()),
+			Error::<Test>::NotCommitter
+		);
+		assert_ok!(PoeModule::reveal_claim(Origin::signed(1), claim.clone(), b"salt".to_vec()));
+	})
+}
+
+#[test]
+fn expired_commitment_can_not_be_revealed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		let hash = commitment(&claim, b"salt");
+		assert_ok!(PoeModule::commit_claim(Origin::signed(1), hash));
+
+		System::set_block_number(9);
+		assert_noop!(
+			PoeModule::reveal_claim(Origin::signed(1), claim.clone(), b"salt".to_vec()),
+			Error::<Test>::CommitmentExpired
+		);
+		assert_ok!(PoeModule::commit_claim(Origin::signed(2), hash));
+	})
+}
+
+#[test]
+fn claim_records_returns_parallel_records() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![3]));
+
+		let records = PoeModule::claim_records(vec![vec![1], vec![2], vec![3]]);
+		assert_eq!(records.len(), 3);
+		assert_eq!(records[0].as_ref().map(|r| r.owner), Some(1));
+		assert_eq!(records[1], None);
+		assert_eq!(records[2].as_ref().map(|r| r.owner), Some(2));
+	})
+}
+
+#[test]
+fn claim_records_caps_input_length() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![1]; MAX_QUERY_LIMIT as usize + 5];
+		assert_eq!(PoeModule::claim_records(claims).len(), MAX_QUERY_LIMIT as usize);
+	})
+}
+
+#[test]
+fn transfer_claim_cancels_lease_by_default() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 3, 8));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::lease(&claim), None);
+		assert_eq!(PoeModule::claim_controller(&claim), Some(2));
+	})
+}
+
+#[test]
+fn transfer_claim_can_carry_lease_over() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 3, 8));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, true));
+		assert_eq!(PoeModule::lease(&claim), Some((3, 8)));
+		assert_eq!(PoeModule::claim_controller(&claim), Some(3));
+		assert_noop!(
+			PoeModule::cancel_lease(Origin::signed(1), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::cancel_lease(Origin::signed(2), claim));
+	})
+}
+
+#[test]
+fn prove_control_postpones_dormancy() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		System::set_block_number(11);
+		assert_eq!(PoeModule::claim_status(&claim), Some(ClaimStatus::Active));
+		assert_ok!(PoeModule::prove_control(Origin::signed(1), claim.clone(), 7));
+		assert_eq!(PoeModule::last_proven_block(&claim), Some(11));
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::ControlProven(1, claim.clone(), 7)))
+		);
+
+		System::set_block_number(21);
+		assert_eq!(PoeModule::claim_status(&claim), Some(ClaimStatus::Active));
+		System::set_block_number(22);
+		assert_eq!(PoeModule::claim_status(&claim), Some(ClaimStatus::Dormant));
+	})
+}
+
+#[test]
+fn prove_control_fails_for_non_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::prove_control(Origin::signed(2), claim, 0),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn create_claim_reports_existing_owner_on_duplicate() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_eq!(
+			PoeModule::create_claim(Origin::signed(2), claim.clone()),
+			Err(Error::<Test>::ProofAlreadyExist.into())
+		);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::ClaimCreationRejected(claim, 1)))
+		);
+	})
+}
+
+#[test]
+fn requested_transfer_pays_owner_on_approval() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(4), claim.clone()));
+
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 4));
+		assert_eq!(Balances::reserved_balance(3), 10);
+		assert_noop!(
+			PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 4),
+			Error::<Test>::TransferAlreadyRequested
+		);
+		assert_noop!(
+			PoeModule::approve_incoming_transfer(Origin::signed(2), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::approve_incoming_transfer(Origin::signed(4), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).0, 3);
+		assert_eq!(PoeModule::transfer_request(&claim), None);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 90);
+		assert_eq!(Balances::free_balance(4), 15);
+	})
+}
+
+#[test]
+fn approval_fails_when_owner_can_not_pay_transfer_fee() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(4), claim.clone()));
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 4));
+
+		// account 4 holds 5, not enough for the transfer fee before the request fee is paid out
+		set_transfer_fee(10);
+		assert_noop!(
+			PoeModule::approve_incoming_transfer(Origin::signed(4), claim.clone()),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(Proofs::<Test>::get(&claim).0, 4);
+		assert!(PoeModule::transfer_request(&claim).is_some());
+		assert_eq!(Balances::reserved_balance(3), 10);
+		assert_eq!(Balances::free_balance(4), 5);
+
+		// keeping the account alive counts too
+		set_transfer_fee(5);
+		assert_noop!(
+			PoeModule::approve_incoming_transfer(Origin::signed(4), claim.clone()),
+			Error::<Test>::InsufficientBalance
+		);
+
+		set_transfer_fee(4);
+		assert_ok!(PoeModule::approve_incoming_transfer(Origin::signed(4), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).0, 3);
+		assert_eq!(PoeModule::transfer_request(&claim), None);
+		assert_eq!(Balances::free_balance(4), 5 + 10 - 4);
+		set_transfer_fee(0);
+	});
+}
+
+#[test]
+fn approval_fails_when_fee_can_not_be_paid() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		// account 1 does not exist, so the held fee can not be moved to it
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+
+		assert_noop!(
+			PoeModule::approve_incoming_transfer(Origin::signed(1), claim.clone()),
+			pallet_balances::Error::<Test>::DeadAccount
+		);
+		assert_eq!(Proofs::<Test>::get(&claim).0, 1);
+		assert_eq!(Balances::reserved_balance(3), 10);
+	})
+}
+
+#[test]
+fn transfer_request_is_refunded_when_owner_changes() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(PoeModule::transfer_request(&claim), None);
+		assert_eq!(PoeModule::transfer_request_expires(&claim), None);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_noop!(
+			PoeModule::approve_incoming_transfer(Origin::signed(2), claim.clone()),
+			Error::<Test>::NoTransferRequest
+		);
+	})
+}
+
+#[test]
+fn rejected_transfer_request_refunds_fee() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+
+		assert_ok!(PoeModule::reject_incoming_transfer(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).0, 1);
+		assert_eq!(PoeModule::transfer_request(&claim), None);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_noop!(
+			PoeModule::approve_incoming_transfer(Origin::signed(1), claim),
+			Error::<Test>::NoTransferRequest
+		);
+	})
+}
+
+#[test]
+fn request_claim_transfer_checks_owner_and_funds() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 2),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::request_claim_transfer(Origin::signed(4), claim, 1),
+			Error::<Test>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn claim_debug_dumps_all_claim_state() {
+	new_test_ext().execute_with(|| {
+		use codec::Decode;
+
+		Timestamp::set_timestamp(42);
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim_with_uri(Origin::signed(1), claim.clone(), b"ipfs://Qm1".to_vec()));
+		assert_ok!(PoeModule::set_transfer_unlock(Origin::signed(1), claim.clone(), 3));
+		assert_ok!(PoeModule::lease_claim(Origin::signed(1), claim.clone(), 2, 9));
+		assert_ok!(PoeModule::require_cosign(Origin::signed(1), claim.clone(), 4));
+		assert_ok!(PoeModule::cosign_transfer(Origin::signed(4), claim.clone(), 5));
+		assert_ok!(PoeModule::set_transfer_allowlist(Origin::signed(1), claim.clone(), vec![5]));
+		assert_ok!(PoeModule::prove_control(Origin::signed(1), claim.clone(), 0));
+		assert_ok!(PoeModule::challenge_claim(Origin::signed(3), claim.clone()));
+		assert_ok!(PoeModule::lock_claim(Origin::signed(1), claim.clone(), Some(6)));
+		assert_ok!(PoeModule::approve_scoped(Origin::signed(1), claim.clone(), 2, true, false));
+
+		let dump = PoeModule::claim_debug(&claim).unwrap();
+		assert_eq!(ClaimDebug::<u64, u64, u64, u64>::decode(&mut &dump[..]).unwrap(), ClaimDebug {
+			owner: 1,
+			block_number: 1,
+			timestamp: Some(42),
+			transfer_unlock: Some(3),
+			activates_at: None,
+			last_transfer_block: None,
+			last_proven_block: Some(1),
+			lease: Some((2, 9)),
+			cosigner: Some(4),
+			cosigned: Some((5, 11)),
+			transfer_allowlist: vec![5],
+			challenge: Some((3, 10)),
+			transfer_request: None,
+			renounced: false,
+			lock: Some(Some(6)),
+			co_owners: None,
+			pending_actions: vec![],
+			scoped_approvals: vec![(2, DelegatePermissions { can_transfer: true, can_revoke: false })],
+			uri: Some(b"ipfs://Qm1".to_vec()),
+		});
+		assert_eq!(PoeModule::claim_debug(&[9]), None);
+	})
+}
+
+#[test]
+fn timed_lock_expires_on_its_own() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lock_claim(Origin::signed(1), claim.clone(), Some(4)));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false),
+			Error::<Test>::ClaimLocked
+		);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false),
+			Error::<Test>::ClaimLocked
+		);
+
+		System::set_block_number(4);
+		assert!(!PoeModule::is_locked(&claim));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+	})
+}
+
+#[test]
+fn indefinite_lock_persists_until_unlocked() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::lock_claim(Origin::signed(1), claim.clone(), None));
+
+		System::set_block_number(1_000);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false),
+			Error::<Test>::ClaimLocked
+		);
+
+		assert_ok!(PoeModule::unlock_claim(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			PoeModule::unlock_claim(Origin::signed(1), claim.clone()),
+			Error::<Test>::ClaimNotLocked
+		);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim, false));
+	})
+}
+
+#[test]
+fn transfer_event_carries_new_owner_nonce() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		System::inc_account_nonce(&2);
+		System::inc_account_nonce(&2);
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2, false));
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::ClaimTransferred(1, 2, claim, 2)))
+		);
+	});
+}
+
+#[test]
+fn create_claim_with_uri_accepts_known_schemes() {
+	new_test_ext().execute_with(|| {
+		for (i, uri) in [&b"http://a.io/x"[..], b"https://a.io/x", b"ipfs://Qm1"].iter().enumerate() {
+			let claim = vec![i as u8];
+			assert_ok!(PoeModule::create_claim_with_uri(Origin::signed(1), claim.clone(), uri.to_vec()));
+			assert_eq!(PoeModule::claim_uri(&claim), Some(uri.to_vec()));
+		}
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![0], false));
+		assert_eq!(PoeModule::claim_uri(&vec![0]), None);
+	})
+}
+
+#[test]
+fn create_claim_with_uri_rejects_unknown_scheme() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::create_claim_with_uri(Origin::signed(1), vec![0, 1], b"ftp://a.io/x".to_vec()),
+			Error::<Test>::InvalidUri
+		);
+	})
+}
+
+#[test]
+fn create_claim_with_uri_bounds_uri_length() {
+	new_test_ext().execute_with(|| {
+		let mut uri = b"https://".to_vec();
+		uri.resize(33, b'a');
+		assert_noop!(
+			PoeModule::create_claim_with_uri(Origin::signed(1), vec![0, 1], uri.clone()),
+			Error::<Test>::UriTooLong
+		);
+
+		uri.truncate(32);
+		assert_ok!(PoeModule::create_claim_with_uri(Origin::signed(1), vec![0, 1], uri));
+	})
+}
+
+#[test]
+fn co_owned_transfer_runs_only_at_threshold() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![3, 1, 2], 2));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 4, false),
+			Error::<Test>::ClaimCoOwned
+		);
+
+		assert_ok!(PoeModule::propose_action(Origin::signed(1), claim.clone(), ClaimAction::Transfer(4)));
+		assert_eq!(Proofs::<Test>::get(&claim).0, 1);
+		assert_noop!(
+			PoeModule::approve_action(Origin::signed(1), claim.clone(), 0),
+			Error::<Test>::AlreadyApproved
+		);
+		assert_noop!(
+			PoeModule::approve_action(Origin::signed(5), claim.clone(), 0),
+			Error::<Test>::NotCoOwner
+		);
+		assert_noop!(
+			PoeModule::approve_action(Origin::signed(2), claim.clone(), 1),
+			Error::<Test>::ActionNotFound
+		);
+
+		assert_ok!(PoeModule::approve_action(Origin::signed(2), claim.clone(), 0));
+		assert_eq!(Proofs::<Test>::get(&claim).0, 4);
+		assert_eq!(PoeModule::co_owners(&claim), None);
+		assert!(PoeModule::pending_actions(&claim).is_empty());
+	})
+}
+
+#[test]
+fn co_owned_revoke_runs_only_at_threshold() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![1, 2, 3], 3));
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false),
+			Error::<Test>::ClaimCoOwned
+		);
+
+		assert_ok!(PoeModule::propose_action(Origin::signed(2), claim.clone(), ClaimAction::Revoke));
+		assert_ok!(PoeModule::approve_action(Origin::signed(3), claim.clone(), 0));
+		assert!(Proofs::<Test>::contains_key(&claim));
+		assert_eq!(PoeModule::pending_actions(&claim)[0].approvals, vec![2, 3]);
+
+		assert_ok!(PoeModule::approve_action(Origin::signed(1), claim.clone(), 0));
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(PoeModule::co_owners(&claim), None);
+	})
+}
+
+#[test]
+fn set_co_owners_checks_threshold() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(
+			PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![1, 2, 2], 3),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![1, 2], 0),
+			Error::<Test>::InvalidThreshold
+		);
+	})
+}
+
+#[test]
+fn ownership_of_reports_present_and_absent_claims() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![3]));
+
+		assert_eq!(
+			PoeModule::ownership_of(vec![vec![1], vec![2], vec![3]]),
+			vec![Some(1), None, Some(2)]
+		);
+	})
+}
+
+#[test]
+fn ownership_of_caps_input_length() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![1]; MAX_QUERY_LIMIT as usize + 5];
+		assert_eq!(PoeModule::ownership_of(claims).len(), MAX_QUERY_LIMIT as usize);
+	})
+}
+
+#[test]
+fn permanently_revoked_claim_can_not_be_created_again() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone(), true));
+		assert!(PoeModule::tombstoned(&claim));
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), claim.clone()),
+			Error::<Test>::ClaimTombstoned
+		);
+	})
+}
+
+#[test]
+fn normally_revoked_claim_can_be_created_again() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone(), false));
+		assert!(!PoeModule::tombstoned(&claim));
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), claim));
+	})
+}
+
+#[test]
+fn claim_deposit_scales_with_length_and_is_refunded() {
+	new_test_ext().execute_with(|| {
+		set_claim_deposit(10, 2);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(3), 14);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![0, 1, 2, 3]));
+		assert_eq!(Balances::reserved_balance(3), 14 + 18);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(3), vec![0, 1], false));
+		assert_eq!(Balances::reserved_balance(3), 18);
+		assert_eq!(Balances::free_balance(3), 100 - 18);
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(4), vec![0, 1]),
+			Error::<Test>::InsufficientBalance
+		);
+
+		set_claim_deposit(0, 0);
+	})
+}
+
+#[test]
+fn effective_ownership_shows_pending_transfer() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_eq!(PoeModule::effective_ownership(&claim), None);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::effective_ownership(&claim), Some((1, None)));
+
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+		assert_eq!(PoeModule::effective_ownership(&claim), Some((1, Some(3))));
+	})
+}
+
+#[test]
+fn force_revoke_owner_claims_clears_portfolio() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		set_claim_deposit(1, 1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![1]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![2, 2]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(4), vec![3]));
+		assert_eq!(Balances::reserved_balance(3), 5);
+
+		let info = PoeModule::force_revoke_owner_claims(Origin::ROOT, 3).unwrap();
+		assert_eq!(info.actual_weight, Some((1 + 2 * REMOVE_CLAIM_READS) + (1 + 2 * REMOVE_CLAIM_WRITES) * 10));
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert!(!Proofs::<Test>::contains_key(vec![2, 2]));
+		assert!(Proofs::<Test>::contains_key(vec![3]));
+		assert!(PoeModule::claims_of(3).is_empty());
+		assert_eq!(OwnedClaimCount::<Test>::get(3), 0);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::OwnerClaimsRevoked(3, 2)))
+		);
+
+		set_claim_deposit(0, 0);
+	})
+}
+
+#[test]
+fn force_revoke_owner_claims_fails_for_non_root() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1]));
+		assert_noop!(
+			PoeModule::force_revoke_owner_claims(Origin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn expired_transfer_request_is_cancelled_and_refunded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+		assert_eq!(Balances::reserved_balance(3), 10);
+
+		// nothing expires yet, only the empty expiry list is read and cleared
+		assert_eq!(PoeModule::on_initialize(10), 1 + 10);
+		assert!(PoeModule::transfer_request(&claim).is_some());
+
+		assert_eq!(PoeModule::on_initialize(11), 4 + 4 * 10);
+		assert_eq!(PoeModule::transfer_request(&claim), None);
+		assert_eq!(PoeModule::transfer_request_expires(&claim), None);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::TransferRequestExpired(3, claim)))
+		);
+	})
+}
+
+#[test]
+fn renewed_transfer_request_outlives_earlier_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+		assert_ok!(PoeModule::reject_incoming_transfer(Origin::signed(1), claim.clone()));
+
+		System::set_block_number(5);
+		assert_ok!(PoeModule::request_claim_transfer(Origin::signed(3), claim.clone(), 1));
+
+		PoeModule::on_initialize(11);
+		assert!(PoeModule::transfer_request(&claim).is_some());
+
+		PoeModule::on_initialize(15);
+		assert_eq!(PoeModule::transfer_request(&claim), None);
+	})
+}
+
+#[test]
+fn transfer_only_delegate_can_not_revoke() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::approve_scoped(Origin::signed(1), claim.clone(), 2, true, false));
+
+		assert_noop!(
+			PoeModule::delegated_revoke_claim(Origin::signed(2), claim.clone()),
+			Error::<Test>::NotPermitted
+		);
+		assert_noop!(
+			PoeModule::delegated_transfer_claim(Origin::signed(5), claim.clone(), 4),
+			Error::<Test>::NotPermitted
+		);
+
+		assert_ok!(PoeModule::delegated_transfer_claim(Origin::signed(2), claim.clone(), 4));
+		assert_eq!(Proofs::<Test>::get(&claim).0, 4);
+		assert!(PoeModule::scoped_approvals(&claim).is_empty());
+	})
+}
+
+#[test]
+fn revoke_only_delegate_can_not_transfer() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::approve_scoped(Origin::signed(1), claim.clone(), 2, false, true));
+
+		assert_noop!(
+			PoeModule::delegated_transfer_claim(Origin::signed(2), claim.clone(), 4),
+			Error::<Test>::NotPermitted
+		);
+
+		assert_ok!(PoeModule::delegated_revoke_claim(Origin::signed(2), claim.clone()));
+		assert!(!Proofs::<Test>::contains_key(&claim));
+	})
+}
+
+#[test]
+fn approve_scoped_without_permissions_removes_delegate() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::approve_scoped(Origin::signed(1), claim.clone(), 2, true, true));
+		assert_ok!(PoeModule::approve_scoped(Origin::signed(1), claim.clone(), 2, false, false));
+
+		assert!(PoeModule::scoped_approvals(&claim).is_empty());
+		assert_noop!(
+			PoeModule::delegated_revoke_claim(Origin::signed(2), claim),
+			Error::<Test>::NotPermitted
+		);
+	})
+}
+
+#[test]
+fn replace_claim_fails_for_locked_claim() {
+	new_test_ext().execute_with(|| {
+		let old_claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), old_claim.clone()));
+		assert_ok!(PoeModule::lock_claim(Origin::signed(1), old_claim.clone(), Some(5)));
+		assert_eq!(PoeModule::claim_record(&old_claim).map(|r| r.lock), Some(Some(Some(5))));
+
+		assert_noop!(
+			PoeModule::replace_claim(Origin::signed(1), old_claim.clone(), vec![2, 3]),
+			Error::<Test>::ClaimLocked
+		);
+
+		System::set_block_number(5);
+		assert_eq!(PoeModule::claim_record(&old_claim).map(|r| r.lock), Some(None));
+		assert_ok!(PoeModule::replace_claim(Origin::signed(1), old_claim, vec![2, 3]));
+	});
+}
+
+#[test]
+fn replace_claim_fails_while_pending() {
+	new_test_ext().execute_with(|| {
+		set_activation_delay(3);
+		let old_claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), old_claim.clone()));
+
+		assert_noop!(
+			PoeModule::replace_claim(Origin::signed(1), old_claim.clone(), vec![2, 3]),
+			Error::<Test>::ClaimPending
+		);
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::replace_claim(Origin::signed(1), old_claim, vec![2, 3]));
+
+		set_activation_delay(0);
+	});
+}
+
+#[test]
+fn co_owned_claim_can_not_be_replaced_or_renounced_alone() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![1, 2], 2));
+
+		assert_noop!(
+			PoeModule::replace_claim(Origin::signed(1), claim.clone(), vec![2, 3]),
+			Error::<Test>::ClaimCoOwned
+		);
+		assert_noop!(
+			PoeModule::renounce_claim(Origin::signed(1), claim.clone()),
+			Error::<Test>::ClaimCoOwned
+		);
+	});
+}
+
+#[test]
+fn propose_action_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![1, 2], 2));
+
+		assert_ok!(PoeModule::propose_action(Origin::signed(1), claim.clone(), ClaimAction::Revoke));
+		assert_ok!(PoeModule::propose_action(Origin::signed(2), claim.clone(), ClaimAction::Transfer(4)));
+		assert_noop!(
+			PoeModule::propose_action(Origin::signed(1), claim.clone(), ClaimAction::Revoke),
+			Error::<Test>::TooManyPendingActions
+		);
+	});
+}
+
+#[test]
+fn replace_claim_moves_deposit_to_new_claim() {
+	new_test_ext().execute_with(|| {
+		set_claim_deposit(10, 2);
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![0, 1]));
+		assert_eq!(Balances::reserved_balance(3), 14);
+
+		assert_ok!(PoeModule::replace_claim(Origin::signed(3), vec![0, 1], vec![2, 3, 4]));
+		assert_eq!(PoeModule::claim_deposit_of(vec![0, 1]), None);
+		assert_eq!(PoeModule::claim_deposit_of(vec![2, 3, 4]), Some((3, 16)));
+		assert_eq!(Balances::reserved_balance(3), 16);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(3), vec![2, 3, 4], false));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+
+		set_claim_deposit(0, 0);
+	});
+}
+
+#[test]
+fn every_signed_creation_path_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		set_claim_deposit(1, 1);
+		assert_ok!(PoeModule::ensure_claim(Origin::signed(3), vec![1]));
+		assert_ok!(PoeModule::create_claim_with_uri(Origin::signed(3), vec![2], b"ipfs://Qm1".to_vec()));
+		assert_ok!(PoeModule::create_derived_claim(Origin::signed(3), vec![3]));
+		assert_eq!(Balances::reserved_balance(3), 2 + 2 + 33);
+
+		set_claim_deposit(0, 0);
+	});
+}
+
+#[test]
+fn create_claim_signed_rejects_replayed_signature() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let signature = TestSignature(2, PoeModule::create_claim_payload(&claim, &2));
+
+		assert_ok!(PoeModule::create_claim_signed(Origin::signed(1), claim.clone(), 2, signature.clone()));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone(), false));
+
+		assert_noop!(
+			PoeModule::create_claim_signed(Origin::signed(1), claim.clone(), 2, signature),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_with_consent_rejects_replayed_consent() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+
+		let consent = TestSignature(2, PoeModule::consent_payload(&claim, &1, &2));
+		assert_ok!(PoeModule::transfer_claim_with_consent(Origin::signed(1), claim.clone(), 2, consent.clone()));
+
+		System::set_block_number(10);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 1, false));
+
+		System::set_block_number(20);
+		assert_noop!(
+			PoeModule::transfer_claim_with_consent(Origin::signed(1), claim.clone(), 2, consent),
+			Error::<Test>::InvalidConsent
+		);
+	});
+}
+
+#[test]
+fn recompute_counters_runs_in_pages() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claims = MAX_QUERY_LIMIT as u64 + 50;
+		for owner in 0..claims {
+			assert_ok!(PoeModule::create_claim(Origin::signed(owner), vec![owner as u8, 0]));
+		}
+		OwnedClaimCount::<Test>::insert(1, 7);
+		TotalClaimBytes::put(1);
+
+		assert_ok!(PoeModule::recompute_counters(Origin::ROOT));
+		assert!(PoeModule::recompute_cursor().is_some());
+		assert!(!System::events().iter().any(|r| r.event == TestEvent::poe(RawEvent::CountersRecomputed)));
+
+		// claims created mid-run are counted exactly once
+		assert_ok!(PoeModule::create_claim(Origin::signed(claims), vec![1, 1]));
+
+		assert_ok!(PoeModule::recompute_counters(Origin::ROOT));
+		assert_eq!(PoeModule::recompute_cursor(), None);
+		assert_eq!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(TestEvent::poe(RawEvent::CountersRecomputed))
+		);
+		assert_eq!(PoeModule::owned_claim_count(1), 1);
+		assert_eq!(PoeModule::owned_claim_count(claims), 1);
+		assert_eq!(PoeModule::total_claim_bytes(), 2 * (claims + 1));
+		assert_eq!(PoeModule::claims_of(claims), vec![vec![1, 1]]);
+	});
+}
+
+#[test]
+fn reveal_claim_stores_hex_claims_decoded() {
+	new_test_ext().execute_with(|| {
+		set_hex_claims(true);
+		System::set_block_number(1);
+		let claim = b"0x0a0b".to_vec();
+		let hash = commitment(&claim, b"salt");
+		assert_ok!(PoeModule::commit_claim(Origin::signed(1), hash));
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::reveal_claim(Origin::signed(1), claim.clone(), b"salt".to_vec()));
+		assert_eq!(Proofs::<Test>::get(&vec![10, 11]), (1, 3));
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(PoeModule::claims_of(1), vec![vec![10, 11]]);
+		set_hex_claims(false);
+	});
+}
+
+#[test]
+fn hex_claims_are_addressed_by_decoded_bytes_after_creation() {
+	new_test_ext().execute_with(|| {
+		set_hex_claims(true);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), b"0x0a0b".to_vec()));
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), b"0x0a0b".to_vec(), false),
+			Error::<Test>::ClaimNotExist
+		);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![10, 11], false));
+		assert!(!Proofs::<Test>::contains_key(&vec![10, 11]));
+		set_hex_claims(false);
+	});
+}
+
+#[test]
+fn claim_uri_counts_towards_storage_budget() {
+	new_test_ext().execute_with(|| {
+		set_max_total_claim_bytes(11);
+		let uri = b"ipfs://Qm1".to_vec();
+
+		assert_noop!(
+			PoeModule::create_claim_with_uri(Origin::signed(1), vec![0, 1], uri.clone()),
+			Error::<Test>::StorageBudgetExceeded
+		);
+		assert_ok!(PoeModule::create_claim_with_uri(Origin::signed(1), vec![0], uri));
+		assert_eq!(PoeModule::total_claim_bytes(), 11);
+
+		TotalClaimBytes::put(0);
+		assert_ok!(PoeModule::recompute_counters(Origin::ROOT));
+		assert_eq!(PoeModule::total_claim_bytes(), 11);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![0], false));
+		assert_eq!(PoeModule::total_claim_bytes(), 0);
+		assert_eq!(PoeModule::claim_uri(&vec![0]), None);
+		set_max_total_claim_bytes(1024);
+	});
+}
+
+#[test]
+fn all_claims_treats_zero_limit_as_maximum() {
+	new_test_ext().execute_with(|| {
+		for owner in 0..(MAX_QUERY_LIMIT as u64 + 1) {
+			assert_ok!(PoeModule::create_claim(Origin::signed(owner), vec![owner as u8]));
+		}
+
+		let (page, next) = PoeModule::all_claims(None, 0);
+		assert_eq!(page.len(), MAX_QUERY_LIMIT as usize);
+		assert!(next.is_some());
+
+		let (page, next) = PoeModule::all_claims(next, 0);
+		assert_eq!(page.len(), 1);
+		assert_eq!(next, None);
+	});
+}
+
+#[test]
+fn runtime_upgrade_rehashes_legacy_proofs_keys_once() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{storage::migration::{get_storage_value, put_storage_value}, traits::OnRuntimeUpgrade};
+
+		let legacy = vec![0, 1];
+		let encoded = legacy.encode();
+		let legacy_key = [&sp_io::hashing::twox_64(&encoded)[..], &encoded[..]].concat();
+		put_storage_value(b"TemplateModule", b"Proofs", &legacy_key, (1u64, 3u64));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![2, 3]));
+
+		PoeModule::on_runtime_upgrade();
+
+		assert_eq!(Proofs::<Test>::get(&legacy), (1, 3));
+		assert_eq!(Proofs::<Test>::get(&vec![2, 3]), (2, 0));
+		assert_eq!(get_storage_value::<(u64, u64)>(b"TemplateModule", b"Proofs", &legacy_key), None);
+		assert!(PoeModule::proofs_hasher_migrated());
+
+		// later upgrades do not scan the map again
+		put_storage_value(b"TemplateModule", b"Proofs", &legacy_key, (1u64, 4u64));
+		assert_eq!(PoeModule::on_runtime_upgrade(), 1);
+		assert_eq!(get_storage_value::<(u64, u64)>(b"TemplateModule", b"Proofs", &legacy_key), Some((1, 4)));
+	});
+}
+
+#[test]
+fn all_claims_ignores_start_key_outside_proofs() {
+	new_test_ext().execute_with(|| {
+		for claim in vec![vec![1], vec![2], vec![3]] {
+			assert_ok!(PoeModule::create_claim(Origin::signed(1), claim));
+		}
+
+		let first_page = PoeModule::all_claims(None, 2);
+		assert_eq!(first_page.0.len(), 2);
+		assert_eq!(PoeModule::all_claims(Some(vec![0xff; 40]), 2), first_page);
+		assert_eq!(PoeModule::all_claims(Some(vec![]), 2), first_page);
+	});
+}
+
+#[test]
+fn renounce_claim_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		set_claim_deposit(1, 1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(Balances::reserved_balance(3), 3);
+
+		assert_ok!(PoeModule::renounce_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(PoeModule::claim_deposit_of(&claim), None);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+
+		set_claim_deposit(0, 0);
+	});
+}
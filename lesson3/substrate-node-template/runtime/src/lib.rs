@@ -66,6 +66,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// Timestamp of a block, in milliseconds.
+pub type Moment = u64;
+
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
@@ -216,7 +219,7 @@ parameter_types! {
 
 impl timestamp::Trait for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
 }
@@ -260,6 +263,26 @@ impl template::Trait for Runtime {
 // 附加题答案
 parameter_types! {
 	pub const MaxClaimLength: u32 = 6;
+	pub const EmitPoeEvents: bool = true;
+	pub const MaxClaimsPerOwner: u32 = 100;
+	pub const CountClaimChars: bool = false;
+	pub const MaxTotalClaimBytes: u64 = 1024 * 1024;
+	pub const MaxBookmarks: u32 = 32;
+	pub const RequireExistingAccount: bool = false;
+	pub const TransferCooldown: BlockNumber = 10;
+	pub const ChallengeBond: Balance = 10_000;
+	pub const PoeTransferFee: Balance = 0;
+	pub const HexClaims: bool = false;
+	pub const ActivationDelay: BlockNumber = 0;
+	pub const RevealDelay: BlockNumber = 1;
+	pub const RevealWindow: BlockNumber = 100;
+	pub const ProofOfControlInterval: BlockNumber = 30 * DAYS;
+	pub const IncomingTransferFee: Balance = 1_000;
+	pub const MaxUriLength: u32 = 256;
+	pub const BaseDeposit: Balance = 1_000;
+	pub const PerByteDeposit: Balance = 10;
+	pub const RequestExpiry: BlockNumber = 7 * DAYS;
+	pub const MaxPendingActions: u32 = 16;
 }
 
 impl poe::Trait for Runtime {
@@ -267,6 +290,30 @@ impl poe::Trait for Runtime {
 	
 	// 附加题答案
 	type MaxClaimLength = MaxClaimLength;
+	type EmitEvents = EmitPoeEvents;
+	type Signature = Signature;
+	type Public = <Signature as Verify>::Signer;
+	type MaxClaimsPerOwner = MaxClaimsPerOwner;
+	type CountClaimChars = CountClaimChars;
+	type MaxTotalClaimBytes = MaxTotalClaimBytes;
+	type MaxBookmarks = MaxBookmarks;
+	type RequireExistingAccount = RequireExistingAccount;
+	type TransferCooldown = TransferCooldown;
+	type Currency = balances::Module<Runtime>;
+	type ChallengeBond = ChallengeBond;
+	type TransferFee = PoeTransferFee;
+	type TransferFeeDestination = ();
+	type HexClaims = HexClaims;
+	type ActivationDelay = ActivationDelay;
+	type RevealDelay = RevealDelay;
+	type RevealWindow = RevealWindow;
+	type ProofOfControlInterval = ProofOfControlInterval;
+	type IncomingTransferFee = IncomingTransferFee;
+	type MaxUriLength = MaxUriLength;
+	type BaseDeposit = BaseDeposit;
+	type PerByteDeposit = PerByteDeposit;
+	type RequestExpiry = RequestExpiry;
+	type MaxPendingActions = MaxPendingActions;
 }
 
 construct_runtime!(
@@ -424,4 +471,61 @@ impl_runtime_apis! {
 			None
 		}
 	}
+
+	impl poe_rpc_runtime_api::PoeApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn claim_length_histogram() -> Vec<(u32, u32)> {
+			PoeModule::claim_length_buckets()
+		}
+
+		fn claims_with_prefix(prefix: Vec<u8>, limit: u32) -> Vec<Vec<u8>> {
+			PoeModule::claims_with_prefix(prefix, limit)
+		}
+
+		fn claim_bounds() -> (Option<(Vec<u8>, BlockNumber)>, Option<(Vec<u8>, BlockNumber)>) {
+			PoeModule::claim_bounds()
+		}
+
+		fn claim_record(claim: Vec<u8>) -> Option<poe::ClaimRecord<AccountId, BlockNumber>> {
+			PoeModule::claim_record(&claim)
+		}
+
+		fn effective_ownership(claim: Vec<u8>) -> Option<(AccountId, Option<AccountId>)> {
+			PoeModule::effective_ownership(&claim)
+		}
+
+		fn claim_records(claims: Vec<Vec<u8>>) -> Vec<Option<poe::ClaimRecord<AccountId, BlockNumber>>> {
+			PoeModule::claim_records(claims)
+		}
+
+		fn ownership_of(claims: Vec<Vec<u8>>) -> Vec<Option<AccountId>> {
+			PoeModule::ownership_of(claims)
+		}
+
+		fn claim_debug(claim: Vec<u8>) -> Option<Vec<u8>> {
+			PoeModule::claim_debug(&claim)
+		}
+
+		fn errors() -> Vec<(u8, Vec<u8>)> {
+			PoeModule::error_names()
+		}
+
+		fn claim_timestamp(claim: Vec<u8>) -> Option<Moment> {
+			PoeModule::claim_timestamp(&claim)
+		}
+
+		fn all_claims(
+			start_key: Option<Vec<u8>>,
+			limit: u32,
+		) -> (Vec<(Vec<u8>, AccountId, BlockNumber)>, Option<Vec<u8>>) {
+			PoeModule::all_claims(start_key, limit)
+		}
+
+		fn owner_stats(owner: AccountId) -> (u32, Option<BlockNumber>, Option<BlockNumber>) {
+			PoeModule::owner_stats(&owner)
+		}
+
+		fn claim_status(claim: Vec<u8>) -> Option<poe::ClaimStatus> {
+			PoeModule::claim_status(&claim)
+		}
+	}
 }
@@ -9,8 +9,10 @@
 /// For more guidance on Substrate FRAME, see the example pallet
 /// https://github.com/paritytech/substrate/blob/master/frame/example/src/lib.rs
 
-use frame_support::{debug, decl_module, decl_storage, decl_event, decl_error, dispatch};
-use frame_system::{self as system, ensure_signed};
+use codec::Encode;
+use frame_support::{debug, decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
+use frame_system::{self as system, ensure_root, ensure_signed};
+use sp_core::offchain::{StorageKind, Timestamp};
 
 #[cfg(test)]
 mod mock;
@@ -24,8 +26,17 @@ pub trait Trait: system::Trait {
 
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// Whether offchain worker cycles leave start and finish markers in offchain storage.
+	type WorkerTracing: Get<bool>;
 }
 
+/// Offchain storage key of the `(block_number, unix_millis)` written when a worker cycle starts.
+pub const WORKER_START_KEY: &[u8] = b"template::worker-start";
+
+/// Offchain storage key of the `(block_number, duration_millis)` written when a worker cycle ends.
+pub const WORKER_FINISH_KEY: &[u8] = b"template::worker-finish";
+
 // This pallet's storage items.
 decl_storage! {
 	// It is important to update your storage name so that your pallet's
@@ -36,6 +47,8 @@ decl_storage! {
 		// Here we are declaring a StorageValue, `Something` as a Option<u32>
 		// `get(fn something)` is the default getter which returns either the stored `u32` or `None` if nothing stored
 		Something get(fn something): Option<u32>;
+		/// Whether the offchain worker runs at all.
+		WorkerEnabled get(fn worker_enabled): bool = true;
 	}
 }
 
@@ -84,13 +97,61 @@ decl_module! {
 			Ok(())
 		}
 
+		/// Turn the offchain worker on or off.
+		#[weight = 10_000]
+		pub fn set_worker_enabled(origin, enabled: bool) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			WorkerEnabled::put(enabled);
+
+			Ok(())
+		}
+
 		fn offchain_worker(block_number: T::BlockNumber) {
+			if !Self::worker_enabled() {
+				debug::info!("Off-chain worker disabled, skipping");
+				return;
+			}
+
 			debug::info!("Entering off-chain workers");
+			let started = Self::trace_start(block_number);
 
 			/*******
 			 * 学员们在这里追加逻辑
 			 *******/
+
+			Self::trace_finish(block_number, started);
 		}
 
 	}
 }
+
+impl<T: Trait> Module<T> {
+	/// Mark the start of a worker cycle, returning its start time if tracing is enabled.
+	fn trace_start(block_number: T::BlockNumber) -> Option<Timestamp> {
+		if !T::WorkerTracing::get() {
+			return None;
+		}
+		let now = sp_io::offchain::timestamp();
+		debug::info!("Worker cycle for block {:?} started at {}", block_number, now.unix_millis());
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			WORKER_START_KEY,
+			&(block_number, now.unix_millis()).encode(),
+		);
+		Some(now)
+	}
+
+	/// Mark the end of a worker cycle started at `started`.
+	fn trace_finish(block_number: T::BlockNumber, started: Option<Timestamp>) {
+		if let Some(started) = started {
+			let duration = sp_io::offchain::timestamp().diff(&started).millis();
+			debug::info!("Worker cycle for block {:?} finished after {} ms", block_number, duration);
+			sp_io::offchain::local_storage_set(
+				StorageKind::PERSISTENT,
+				WORKER_FINISH_KEY,
+				&(block_number, duration).encode(),
+			);
+		}
+	}
+}
@@ -1,8 +1,9 @@
 // Creating mock runtime here
 
 use crate::{Module, Trait};
+use std::cell::RefCell;
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, parameter_types, traits::Get, weights::Weight};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
@@ -48,8 +49,25 @@ impl system::Trait for Test {
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 }
+
+thread_local! {
+	static WORKER_TRACING: RefCell<bool> = RefCell::new(false);
+}
+
+pub struct WorkerTracing;
+impl Get<bool> for WorkerTracing {
+	fn get() -> bool {
+		WORKER_TRACING.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_worker_tracing(enabled: bool) {
+	WORKER_TRACING.with(|v| *v.borrow_mut() = enabled);
+}
+
 impl Trait for Test {
 	type Event = ();
+	type WorkerTracing = WorkerTracing;
 }
 pub type TemplateModule = Module<Test>;
 
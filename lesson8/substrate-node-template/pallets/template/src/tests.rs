@@ -1,7 +1,10 @@
 // Tests to be written here
 
-use crate::{Error, mock::*};
+use crate::{Error, WORKER_FINISH_KEY, WORKER_START_KEY, mock::*};
+use codec::Decode;
 use frame_support::{assert_ok, assert_noop};
+use sp_core::offchain::{OffchainExt, StorageKind, testing::TestOffchainExt};
+use sp_runtime::{DispatchError, traits::OffchainWorker};
 
 #[test]
 fn test_onchain() {
@@ -16,3 +19,67 @@ fn test_offchain() {
 		// Test offchain worker here
 	});
 }
+
+fn offchain_marker(key: &[u8]) -> Option<(u64, u64)> {
+	sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, key)
+		.map(|raw| Decode::decode(&mut &raw[..]).unwrap())
+}
+
+#[test]
+fn offchain_worker_writes_trace_markers_when_enabled() {
+	let (offchain, state) = TestOffchainExt::new();
+	state.write().timestamp = 1_000;
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+
+	t.execute_with(|| {
+		set_worker_tracing(true);
+		TemplateModule::offchain_worker(7);
+
+		assert_eq!(offchain_marker(WORKER_START_KEY), Some((7, 1_000)));
+		assert_eq!(offchain_marker(WORKER_FINISH_KEY), Some((7, 0)));
+		set_worker_tracing(false);
+	});
+}
+
+#[test]
+fn offchain_worker_writes_no_trace_markers_when_disabled() {
+	let (offchain, _state) = TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+
+	t.execute_with(|| {
+		TemplateModule::offchain_worker(7);
+
+		assert_eq!(offchain_marker(WORKER_START_KEY), None);
+		assert_eq!(offchain_marker(WORKER_FINISH_KEY), None);
+	});
+}
+
+#[test]
+fn offchain_worker_does_nothing_when_disabled() {
+	let (offchain, _state) = TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+
+	t.execute_with(|| {
+		set_worker_tracing(true);
+		assert_ok!(TemplateModule::set_worker_enabled(Origin::ROOT, false));
+		TemplateModule::offchain_worker(7);
+
+		assert_eq!(offchain_marker(WORKER_START_KEY), None);
+		assert_eq!(offchain_marker(WORKER_FINISH_KEY), None);
+		set_worker_tracing(false);
+	});
+}
+
+#[test]
+fn set_worker_enabled_fails_for_non_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set_worker_enabled(Origin::signed(1), false),
+			DispatchError::BadOrigin
+		);
+		assert!(TemplateModule::worker_enabled());
+	});
+}
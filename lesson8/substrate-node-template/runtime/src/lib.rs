@@ -252,9 +252,14 @@ impl sudo::Trait for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const WorkerTracing: bool = false;
+}
+
 /// Used for the module template in `./template.rs`
 impl template::Trait for Runtime {
 	type Event = Event;
+	type WorkerTracing = WorkerTracing;
 }
 
 construct_runtime!(